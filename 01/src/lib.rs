@@ -0,0 +1,282 @@
+//! Day 01: Trebuchet?!
+
+use std::{fmt, ops::Range};
+
+use utilities::{
+    parse::{self, InputError},
+    solution::{ParseError, Solution},
+    text::{Match, Matcher},
+};
+
+/// Digits as themselves.
+pub const DIGITS: [(&str, u32); 10] = [
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+/// Digits spelled out in English. There's no zero in the puzzle.
+pub const ENGLISH: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+/// Token from the vocabulary found in a line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Token<'a> {
+    /// Text of the line that matched.
+    pub text: &'a str,
+    /// Bytes of the line that matched.
+    pub span: Range<usize>,
+    /// Digit the text stands for.
+    pub value: u32,
+}
+
+impl fmt::Display for Token<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} from '{}' at {}..{}",
+            self.value, self.text, self.span.start, self.span.end
+        )
+    }
+}
+
+/// First and last tokens of a line, which make its calibration value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reading<'a> {
+    /// Token that starts earliest in the line.
+    pub first: Token<'a>,
+    /// Token that starts latest in the line.
+    pub last: Token<'a>,
+}
+
+impl Reading<'_> {
+    /// Get the calibration value, the first token as tens and the last as ones.
+    pub fn value(&self) -> u32 {
+        self.first.value * 10 + self.last.value
+    }
+}
+
+impl fmt::Display for Reading<'_> {
+    /// Explain where the value came from.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}, {}", self.value(), self.first, self.last)
+    }
+}
+
+/// Reads calibration values using a vocabulary of tokens and their values.
+#[derive(Clone, Debug)]
+pub struct Calibration {
+    matcher: Matcher,
+    /// Value of each token, in the same order as the matcher's patterns.
+    values: Vec<u32>,
+}
+
+impl Calibration {
+    /// Make a new calibration reader from tokens and their values, like
+    /// [DIGITS] and [ENGLISH] or words from another language.
+    pub fn new<S: AsRef<str>>(vocabulary: impl IntoIterator<Item = (S, u32)>) -> Self {
+        let (tokens, values): (Vec<S>, Vec<u32>) = vocabulary.into_iter().unzip();
+        Self {
+            matcher: Matcher::new(tokens),
+            values,
+        }
+    }
+
+    /// Make a token from a match in the line.
+    fn token<'a>(&self, line: &'a str, found: Match) -> Token<'a> {
+        Token {
+            text: &line[found.span()],
+            span: found.span(),
+            value: self.values[found.pattern],
+        }
+    }
+
+    /// Find the first and last tokens in the line. None if there aren't any.
+    pub fn read<'a>(&self, line: &'a str) -> Option<Reading<'a>> {
+        Some(Reading {
+            first: self.token(line, self.matcher.find_first(line)?),
+            last: self.token(line, self.matcher.find_last(line)?),
+        })
+    }
+
+    /// Sum of the calibration values of every line. Errors on the first line
+    /// without any tokens.
+    pub fn sum(&self, text: &str) -> Result<u32, InputError> {
+        parse::lines(text)
+            .map(|line| {
+                self.read(line.rest())
+                    .map(|reading| reading.value())
+                    .ok_or_else(|| line.error("no calibration value on the line"))
+            })
+            .sum()
+    }
+}
+
+/// Calibration values of one line of the document.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Line {
+    /// Value from digits only. None if the line only has spelled out digits,
+    /// like the lines of the part 2 example.
+    digits: Option<u32>,
+    /// Value from digits and spelled out digits.
+    digits_and_words: u32,
+}
+
+/// Read both calibration values of every line. Errors on a line that has
+/// neither digits nor spelled out digits.
+fn read_lines(text: &str) -> Result<Vec<Line>, InputError> {
+    let digits = Calibration::new(DIGITS);
+    let digits_and_words = Calibration::new(DIGITS.into_iter().chain(ENGLISH));
+    parse::lines(text)
+        .map(|line| {
+            let reading = digits_and_words
+                .read(line.rest())
+                .ok_or_else(|| line.error("no digits or spelled out digits on the line"))?;
+            Ok(Line {
+                digits: digits.read(line.rest()).map(|reading| reading.value()),
+                digits_and_words: reading.value(),
+            })
+        })
+        .collect()
+}
+
+/// Sum of the first and last digit on each line. Lines with only spelled out
+/// digits don't have a value here and add nothing.
+fn part1(lines: &[Line]) -> u32 {
+    lines.iter().filter_map(|line| line.digits).sum()
+}
+
+/// Sum of the first and last digit or digit word on each line.
+fn part2(lines: &[Line]) -> u32 {
+    lines.iter().map(|line| line.digits_and_words).sum()
+}
+
+/// Day 01 solution.
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<Line>;
+    type Answer = u32;
+
+    const TITLE: &'static str = "Trebuchet?!";
+
+    fn parse(input: &str) -> Result<Vec<Line>, ParseError> {
+        Ok(read_lines(input)?)
+    }
+
+    fn part1(input: &Vec<Line>) -> u32 {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<Line>) -> u32 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utilities::solution::Part;
+
+    #[test]
+    fn part1_example01() {
+        Day01::check_example(1, Part::One);
+    }
+
+    #[test]
+    fn test_calibration_value() {
+        let calibration = Calibration::new(DIGITS.into_iter().chain(ENGLISH));
+        let value = |line| calibration.read(line).unwrap().value();
+        assert_eq!(value("two1nine"), 29);
+        assert_eq!(value("eightwothree"), 83);
+        assert_eq!(value("abcone2threexyz"), 13);
+        assert_eq!(value("xtwone3four"), 24);
+        assert_eq!(value("4nineeightseven2"), 42);
+        assert_eq!(value("zoneight234"), 14);
+        assert_eq!(value("7pqrstsixteen"), 76);
+        assert_eq!(value("6sevenpqrstsixteen"), 66);
+        assert_eq!(value("67sevenpqrstsixteen7"), 67);
+        assert_eq!(value("1qlbcrdgg"), 11);
+        assert_eq!(value("eightwo"), 82);
+        assert_eq!(value("oneight"), 18);
+    }
+
+    #[test]
+    fn test_calibration_tokens() {
+        let calibration = Calibration::new(DIGITS.into_iter().chain(ENGLISH));
+        let reading = calibration.read("xtwone3four").unwrap();
+        assert_eq!(
+            reading.first,
+            Token {
+                text: "two",
+                span: 1..4,
+                value: 2
+            }
+        );
+        assert_eq!(reading.last.span, 7..11);
+        assert_eq!(
+            reading.to_string(),
+            "24: 2 from 'two' at 1..4, 4 from 'four' at 7..11"
+        );
+        assert_eq!(calibration.read("nothing"), None);
+
+        let digits = Calibration::new(DIGITS);
+        assert_eq!(digits.read("xtwone3four").unwrap().value(), 33);
+    }
+
+    #[test]
+    fn test_calibration_other_vocabulary() {
+        let german = Calibration::new([("null", 0), ("eins", 1), ("zwei", 2), ("drei", 3)]);
+        assert_eq!(german.read("zweinulldreins").unwrap().value(), 21);
+        let with_zero = Calibration::new(DIGITS.into_iter().chain([("zero", 0)]));
+        assert_eq!(with_zero.sum("5zero\nzero7"), Ok(57));
+        let error = with_zero.sum("5zero\nseven").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let lines = Day01::parse("a1b2\nxoneight").unwrap();
+        assert_eq!(
+            lines,
+            vec![
+                Line {
+                    digits: Some(12),
+                    digits_and_words: 12
+                },
+                Line {
+                    digits: None,
+                    digits_and_words: 18
+                }
+            ]
+        );
+        assert_eq!(part1(&lines), 12);
+        assert_eq!(part2(&lines), 30);
+        let error = Day01::parse("1abc2\npqrstu\n7").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: no digits or spelled out digits on the line"
+        );
+    }
+
+    #[test]
+    fn part2_example01() {
+        Day01::check_example(1, Part::Two);
+    }
+}
@@ -0,0 +1,238 @@
+//! Day 02: Cube Conundrum
+
+use std::collections::BTreeMap;
+
+use utilities::{
+    parse::{self, Cursor, InputError},
+    solution::{ParseError, Solution},
+};
+
+/// Cubes of each color, keyed by color name. Colors that aren't in the bag
+/// count as zero.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Bag {
+    cubes: BTreeMap<String, u32>,
+}
+
+/// Handful of cubes shown from the bag, which is just a smaller bag.
+pub type Draw = Bag;
+
+impl Bag {
+    /// Get how many cubes of a color there are.
+    pub fn get(&self, color: &str) -> u32 {
+        self.cubes.get(color).copied().unwrap_or(0)
+    }
+
+    /// Add cubes of a color.
+    pub fn add(&mut self, color: &str, count: u32) {
+        *self.cubes.entry(color.to_string()).or_default() += count;
+    }
+
+    /// Get the colors in the bag and how many of each.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.cubes
+            .iter()
+            .map(|(color, count)| (color.as_str(), *count))
+    }
+
+    /// Get the most of each color in either bag.
+    pub fn max(&self, other: &Bag) -> Bag {
+        let mut max = self.clone();
+        for (color, count) in other.iter() {
+            let entry = max.cubes.entry(color.to_string()).or_default();
+            *entry = (*entry).max(count);
+        }
+        max
+    }
+
+    /// Whether these cubes could all have come out of a bag holding `limits`.
+    pub fn fits_in(&self, limits: &Bag) -> bool {
+        self.iter().all(|(color, count)| count <= limits.get(color))
+    }
+
+    /// Product of the counts of every color in `colors`, the colors the puzzle
+    /// uses. A color missing from this bag counts as zero.
+    pub fn power(&self, colors: &Bag) -> u64 {
+        colors
+            .iter()
+            .map(|(color, _)| u64::from(self.get(color)))
+            .product()
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<(S, u32)> for Bag {
+    fn from_iter<I: IntoIterator<Item = (S, u32)>>(iter: I) -> Self {
+        let mut bag = Bag::default();
+        for (color, count) in iter {
+            bag.add(color.as_ref(), count);
+        }
+        bag
+    }
+}
+
+/// Bag the elf asks about in part 1. Its colors are the colors the puzzle uses.
+fn part1_limits() -> Bag {
+    Bag::from_iter([("red", 12), ("green", 13), ("blue", 14)])
+}
+
+/// Game and every draw shown during it.
+#[derive(PartialEq, Debug)]
+pub struct Game {
+    id: u32,
+    draws: Vec<Draw>,
+}
+
+impl Game {
+    /// Whether every draw could have come from a bag holding `limits`.
+    pub fn is_possible(&self, limits: &Bag) -> bool {
+        self.draws.iter().all(|draw| draw.fits_in(limits))
+    }
+
+    /// Fewest cubes of each color the bag could have held.
+    pub fn minimum_bag(&self) -> Bag {
+        self.draws
+            .iter()
+            .fold(Bag::default(), |bag, draw| bag.max(draw))
+    }
+}
+
+/// Get the game id from the start of the line.
+fn get_game_id(line: &mut Cursor) -> Result<u32, InputError> {
+    line.literal("Game")?;
+    let id = line.unsigned()?;
+    line.literal(":")?;
+    Ok(id)
+}
+
+/// Get a Draw from the cursor, like `3 blue, 4 red`.
+fn get_draw(text: &mut Cursor) -> Result<Draw, InputError> {
+    let cubes = text.separated_by(",", |cubes| {
+        cubes.key_value(|c| c.unsigned::<u32>(), "", |c| c.word())
+    })?;
+    Ok(cubes
+        .into_iter()
+        .map(|(count, color)| (color, count))
+        .collect())
+}
+
+/// Get draws from the rest of the line.
+fn get_draws(line: &mut Cursor) -> Result<Vec<Draw>, InputError> {
+    line.separated_by(";", get_draw)
+}
+
+/// Sum of the ids of games that were possible with the limits.
+fn part1(games: &[Game], limits: &Bag) -> u32 {
+    games
+        .iter()
+        .filter(|game| game.is_possible(limits))
+        .map(|game| game.id)
+        .sum()
+}
+
+/// Make a Game from a line of the input.
+fn get_game(mut line: Cursor) -> Result<Game, InputError> {
+    let id = get_game_id(&mut line)?;
+    let draws = get_draws(&mut line)?;
+    line.end()?;
+    Ok(Game { id, draws })
+}
+
+/// Sum of the power over `colors` of the fewest cubes needed for each game.
+fn part2(games: &[Game], colors: &Bag) -> u64 {
+    games
+        .iter()
+        .map(|game| game.minimum_bag().power(colors))
+        .sum()
+}
+
+/// Day 02 solution.
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Game>;
+    type Answer = u64;
+
+    const TITLE: &'static str = "Cube Conundrum";
+
+    fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+        Ok(parse::lines(input)
+            .map(get_game)
+            .collect::<Result<Vec<Game>, InputError>>()?)
+    }
+
+    fn part1(input: &Vec<Game>) -> u64 {
+        part1(input, &part1_limits()).into()
+    }
+
+    fn part2(input: &Vec<Game>) -> u64 {
+        part2(input, &part1_limits())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utilities::solution::Part;
+
+    #[test]
+    fn test_get_game_id() {
+        assert_eq!(
+            get_game_id(&mut Cursor::new(
+                "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red"
+            )),
+            Ok(3)
+        );
+        assert_eq!(get_game_id(&mut Cursor::new("Game 300: 1 red")), Ok(300));
+    }
+
+    #[test]
+    fn test_get_draw() {
+        assert_eq!(
+            get_draw(&mut Cursor::new("8 green, 6 blue, 20 red")),
+            Ok(Bag::from_iter([("red", 20), ("green", 8), ("blue", 6)]))
+        );
+        let draw = get_draw(&mut Cursor::new("300 purple, 2 red, 1 red")).unwrap();
+        assert_eq!(draw.get("purple"), 300);
+        assert_eq!(draw.get("red"), 3);
+        assert_eq!(draw.get("reddish"), 0);
+    }
+
+    #[test]
+    fn test_bag() {
+        let a = Bag::from_iter([("red", 4), ("blue", 1)]);
+        let b = Bag::from_iter([("red", 2), ("green", 5)]);
+        let max = a.max(&b);
+        assert_eq!(max, Bag::from_iter([("red", 4), ("green", 5), ("blue", 1)]));
+        assert_eq!(max.power(&part1_limits()), 20);
+        // No green at all makes the power zero.
+        assert_eq!(a.power(&part1_limits()), 0);
+        assert!(a.fits_in(&part1_limits()));
+        assert!(!Bag::from_iter([("red", 13)]).fits_in(&part1_limits()));
+        assert!(!Bag::from_iter([("purple", 1)]).fits_in(&part1_limits()));
+    }
+
+    #[test]
+    fn test_games_with_other_colors() {
+        let games = Day02::parse("Game 1: 2 purple, 1 red; 3 purple\nGame 2: 1 red").unwrap();
+        let limits = Bag::from_iter([("red", 1), ("purple", 2)]);
+        assert_eq!(part1(&games, &limits), 2);
+        // Game 2 never shows purple, so its power is zero.
+        assert_eq!(part2(&games, &limits), 3);
+    }
+
+    #[test]
+    fn test_parse_reports_position() {
+        let error = Day02::parse("Game 1: 1 red\nGame 2: 3 blue, purple").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 17: expected a number");
+    }
+
+    #[test]
+    fn part1_example01() {
+        Day02::check_example(2, Part::One);
+    }
+
+    #[test]
+    fn part2_example01() {
+        Day02::check_example(2, Part::Two);
+    }
+}
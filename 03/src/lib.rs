@@ -0,0 +1,451 @@
+//! Day 03: Gear Ratios
+
+use std::{error::Error, fmt};
+use utilities::{
+    color_text::{dim, green, red},
+    coord::Coord,
+    grid::{Grid, Highlighted, Ragged},
+    parse::InputError,
+    solution::{ParseError, Solution},
+};
+
+/// A single cell of the engine schematic.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Cell {
+    /// Digit that is part of a number.
+    Digit(u8),
+    /// Any symbol that isn't a digit or a period.
+    Symbol(char),
+    /// Period, nothing here.
+    Empty,
+}
+
+impl Cell {
+    /// Make a Cell from a character of the schematic.
+    fn from_char(character: char) -> Self {
+        match character {
+            '.' => Cell::Empty,
+            c if c.is_ascii_digit() => Cell::Digit(c as u8 - b'0'),
+            c => Cell::Symbol(c),
+        }
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cell::Digit(digit) => write!(f, "{digit}"),
+            Cell::Symbol(symbol) => write!(f, "{symbol}"),
+            Cell::Empty => write!(f, "."),
+        }
+    }
+}
+
+/// Number written across consecutive cells of a row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NumberSpan {
+    /// Value of the number.
+    pub value: u32,
+    /// Coordinate of the first digit.
+    pub start: Coord,
+    /// How many digits the number has.
+    pub len: usize,
+}
+
+impl NumberSpan {
+    /// Iterate the coordinates of the digits from left to right.
+    pub fn coords(&self) -> impl Iterator<Item = Coord> + use<> {
+        let start = self.start;
+        (0..self.len as i32).map(move |x| Coord::new(start.x + x, start.y))
+    }
+}
+
+impl fmt::Display for NumberSpan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} @ {}", self.value, self.start)
+    }
+}
+
+/// Symbol on the schematic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Symbol {
+    /// The symbol's character.
+    pub symbol: char,
+    /// Where it is.
+    pub coord: Coord,
+}
+
+/// Number on the schematic with too many digits to fit in a u32.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NumberTooBig {
+    /// Coordinate of the number's first digit.
+    pub start: Coord,
+}
+
+impl fmt::Display for NumberTooBig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "number at {} is too big", self.start)
+    }
+}
+
+impl Error for NumberTooBig {}
+
+/// Engine schematic with every number and symbol found once up front, so the
+/// queries only look up what's next to what.
+#[derive(Clone, Debug)]
+pub struct Schematic {
+    grid: Grid<Cell>,
+    /// Numbers in row-major order of their first digit.
+    numbers: Vec<NumberSpan>,
+    /// Symbols in row-major order.
+    symbols: Vec<Symbol>,
+    /// Indexes into `numbers` next to each symbol, in order.
+    numbers_by_symbol: Vec<Vec<usize>>,
+    /// Indexes into `symbols` next to each number, in order.
+    symbols_by_number: Vec<Vec<usize>>,
+}
+
+impl Schematic {
+    /// Make a new Schematic by finding every number and symbol on the grid.
+    /// Errors if a number doesn't fit in a u32.
+    pub fn new(grid: Grid<Cell>) -> Result<Self, NumberTooBig> {
+        let mut numbers: Vec<NumberSpan> = Vec::new();
+        let mut symbols: Vec<Symbol> = Vec::new();
+        // Number each digit belongs to, to look up the neighbours of symbols.
+        let mut number_at: Grid<Option<usize>> = grid.map(|_| None);
+        for (y, row) in grid.rows().enumerate() {
+            let mut current: Option<usize> = None;
+            for (x, cell) in row.iter().enumerate() {
+                let coord = Coord::new(grid.min_x() + x as i32, grid.min_y() + y as i32);
+                match cell {
+                    Cell::Digit(digit) => {
+                        let index = *current.get_or_insert_with(|| {
+                            numbers.push(NumberSpan {
+                                value: 0,
+                                start: coord,
+                                len: 0,
+                            });
+                            numbers.len() - 1
+                        });
+                        let number = &mut numbers[index];
+                        number.value = number
+                            .value
+                            .checked_mul(10)
+                            .and_then(|value| value.checked_add(u32::from(*digit)))
+                            .ok_or(NumberTooBig {
+                                start: number.start,
+                            })?;
+                        number.len += 1;
+                        number_at[coord] = Some(index);
+                        continue;
+                    }
+                    Cell::Symbol(symbol) => symbols.push(Symbol {
+                        symbol: *symbol,
+                        coord,
+                    }),
+                    Cell::Empty => {}
+                }
+                current = None;
+            }
+        }
+
+        let mut symbols_by_number = vec![Vec::new(); numbers.len()];
+        let numbers_by_symbol: Vec<Vec<usize>> = symbols
+            .iter()
+            .enumerate()
+            .map(|(index, symbol)| {
+                let mut adjacent: Vec<usize> = symbol
+                    .coord
+                    .neighbors8()
+                    .filter_map(|coord| number_at.get(&coord).copied().flatten())
+                    .collect();
+                adjacent.sort_unstable();
+                adjacent.dedup();
+                for number in &adjacent {
+                    symbols_by_number[*number].push(index);
+                }
+                adjacent
+            })
+            .collect();
+
+        Ok(Self {
+            grid,
+            numbers,
+            symbols,
+            numbers_by_symbol,
+            symbols_by_number,
+        })
+    }
+
+    /// Get the grid the schematic was made from.
+    pub fn grid(&self) -> &Grid<Cell> {
+        &self.grid
+    }
+
+    /// Get every number in row-major order.
+    pub fn numbers(&self) -> &[NumberSpan] {
+        &self.numbers
+    }
+
+    /// Get every symbol in row-major order.
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// Iterate the numbers next to at least one symbol.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &NumberSpan> {
+        self.numbers
+            .iter()
+            .zip(&self.symbols_by_number)
+            .filter(|(_, symbols)| !symbols.is_empty())
+            .map(|(number, _)| number)
+    }
+
+    /// Iterate the numbers next to at least one `symbol`.
+    pub fn numbers_next_to(&self, symbol: char) -> impl Iterator<Item = &NumberSpan> {
+        self.numbers
+            .iter()
+            .zip(&self.symbols_by_number)
+            .filter(move |(_, symbols)| {
+                symbols
+                    .iter()
+                    .any(|index| self.symbols[*index].symbol == symbol)
+            })
+            .map(|(number, _)| number)
+    }
+
+    /// Iterate the symbols next to exactly `count` numbers, along with those
+    /// numbers.
+    pub fn symbols_with(&self, count: usize) -> impl Iterator<Item = (&Symbol, Vec<&NumberSpan>)> {
+        self.symbols
+            .iter()
+            .zip(&self.numbers_by_symbol)
+            .filter(move |(_, numbers)| numbers.len() == count)
+            .map(|(symbol, numbers)| {
+                let numbers = numbers.iter().map(|index| &self.numbers[*index]).collect();
+                (symbol, numbers)
+            })
+    }
+
+    /// Iterate the gears: `*` symbols next to exactly two numbers.
+    pub fn gears(&self) -> impl Iterator<Item = (&Symbol, Vec<&NumberSpan>)> {
+        self.symbols_with(2)
+            .filter(|(symbol, _)| symbol.symbol == '*')
+    }
+
+    /// View of the grid for printing with the part numbers in green, gears and
+    /// the numbers next to them in red, and numbers that aren't parts dimmed.
+    pub fn highlighted(&self) -> Highlighted<'_, Cell> {
+        let gears: Vec<(&Symbol, Vec<&NumberSpan>)> = self.gears().collect();
+        self.grid
+            .highlighted()
+            .layer(self.numbers.iter().flat_map(NumberSpan::coords), dim)
+            .layer(self.part_numbers().flat_map(NumberSpan::coords), green)
+            .layer(
+                gears.iter().flat_map(|(gear, numbers)| {
+                    numbers
+                        .iter()
+                        .flat_map(|number| number.coords())
+                        .chain([gear.coord])
+                }),
+                red,
+            )
+    }
+}
+
+/// Get sum of numbers touching a symbol.
+fn part1(schematic: &Schematic) -> u64 {
+    schematic
+        .part_numbers()
+        .map(|number| u64::from(number.value))
+        .sum()
+}
+
+/// Get gear ratio of pairs of numbers touching a * symbol.
+fn part2(schematic: &Schematic) -> u64 {
+    schematic
+        .gears()
+        .map(|(_, numbers)| {
+            numbers
+                .iter()
+                .map(|number| u64::from(number.value))
+                .product::<u64>()
+        })
+        .sum()
+}
+
+/// Day 03 solution.
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Schematic;
+    type Answer = u64;
+
+    const TITLE: &'static str = "Gear Ratios";
+
+    fn parse(input: &str) -> Result<Schematic, ParseError> {
+        let grid = Grid::try_parse_with(input, Ragged::Reject, |c| Some(Cell::from_char(c)))?;
+        // The grid starts at the origin, so coordinates are columns and lines
+        // counting from zero.
+        Ok(Schematic::new(grid).map_err(|e| InputError {
+            line: e.start.y as usize + 1,
+            column: e.start.x as usize + 1,
+            message: e.to_string(),
+        })?)
+    }
+
+    fn part1(input: &Schematic) -> u64 {
+        part1(input)
+    }
+
+    fn part2(input: &Schematic) -> u64 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utilities::solution::Part;
+
+    /// Load the schematic from a file.
+    fn load_schematic(file_name: &str) -> Schematic {
+        Schematic::new(Grid::parse_file_with(file_name, Cell::from_char)).unwrap()
+    }
+
+    #[test]
+    fn part1_example01() {
+        Day03::check_example(3, Part::One);
+    }
+
+    #[test]
+    #[ignore = "needs the puzzle input"]
+    fn test_part1() {
+        Day03::check_input(3, Part::One);
+    }
+
+    #[test]
+    fn part2_example2() {
+        Day03::check_example(3, Part::Two);
+    }
+
+    #[test]
+    #[ignore = "needs the puzzle input"]
+    fn test_part2() {
+        Day03::check_input(3, Part::Two);
+    }
+
+    #[test]
+    fn test_cell_from_char() {
+        assert_eq!(Cell::from_char('7'), Cell::Digit(7));
+        assert_eq!(Cell::from_char('*'), Cell::Symbol('*'));
+        assert_eq!(Cell::from_char('.'), Cell::Empty);
+    }
+
+    #[test]
+    fn test_schematic_numbers_and_symbols() {
+        let schematic = load_schematic("example.txt");
+        assert_eq!(schematic.numbers().len(), 10);
+        assert_eq!(
+            schematic.numbers()[0],
+            NumberSpan {
+                value: 467,
+                start: Coord::new(0, 0),
+                len: 3
+            }
+        );
+        assert_eq!(
+            schematic.numbers()[0].coords().collect::<Vec<Coord>>(),
+            vec![Coord::new(0, 0), Coord::new(1, 0), Coord::new(2, 0)]
+        );
+        // Number running into the end of the row.
+        assert_eq!(schematic.numbers()[3].value, 633);
+        assert_eq!(schematic.numbers()[3].start, Coord::new(7, 2));
+        assert_eq!(
+            schematic.symbols()[1],
+            Symbol {
+                symbol: '#',
+                coord: Coord::new(6, 3)
+            }
+        );
+        assert_eq!(schematic.symbols().len(), 6);
+    }
+
+    #[test]
+    fn test_schematic_queries() {
+        let schematic = load_schematic("example.txt");
+        let values = |numbers: Vec<&NumberSpan>| -> Vec<u32> {
+            numbers.iter().map(|number| number.value).collect()
+        };
+        assert_eq!(
+            values(schematic.part_numbers().collect()),
+            vec![467, 35, 633, 617, 592, 755, 664, 598]
+        );
+        assert_eq!(values(schematic.numbers_next_to('#').collect()), vec![633]);
+        assert_eq!(
+            values(schematic.numbers_next_to('*').collect()),
+            vec![467, 35, 617, 755, 598]
+        );
+
+        let gears: Vec<(Coord, Vec<u32>)> = schematic
+            .gears()
+            .map(|(symbol, numbers)| (symbol.coord, values(numbers)))
+            .collect();
+        assert_eq!(
+            gears,
+            vec![
+                (Coord::new(3, 1), vec![467, 35]),
+                (Coord::new(5, 8), vec![755, 598])
+            ]
+        );
+        let lonely: Vec<Coord> = schematic
+            .symbols_with(1)
+            .map(|(symbol, _)| symbol.coord)
+            .collect();
+        assert_eq!(
+            lonely,
+            vec![
+                Coord::new(6, 3),
+                Coord::new(3, 4),
+                Coord::new(5, 5),
+                Coord::new(3, 8)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_highlighted() {
+        let schematic = load_schematic("example.txt");
+        let plain = schematic.grid().to_string();
+        assert!(plain.ends_with(".664.598.."));
+        let highlighted = schematic.highlighted().to_string();
+        let last = highlighted.lines().last().unwrap();
+        assert!(last.contains(&format!("{}{}{}", green("6"), green("6"), green("4"))));
+        assert!(last.contains(&format!("{}{}{}", red("5"), red("9"), red("8"))));
+        let first = highlighted.lines().nth(3).unwrap();
+        assert!(first.contains(&format!("..{}{}{}..", dim("1"), dim("1"), dim("4"))));
+    }
+
+    #[test]
+    fn test_number_next_to_several_symbols() {
+        let grid = Grid::parse_with("*12*\n.$..", Cell::from_char);
+        let schematic = Schematic::new(grid).unwrap();
+        let (_, numbers) = schematic.symbols_with(1).next().unwrap();
+        assert_eq!(numbers[0].value, 12);
+        assert_eq!(schematic.symbols_with(1).count(), 3);
+        assert_eq!(schematic.part_numbers().count(), 1);
+    }
+
+    #[test]
+    fn test_long_digit_run() {
+        let error = Day03::parse("..............\n.111111111111*").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: number at (1, 1) is too big"
+        );
+        // Biggest u32 still fits, and gear ratios of big numbers don't overflow.
+        let schematic = Day03::parse("4294967295*4294967295").unwrap();
+        assert_eq!(part1(&schematic), 2 * 4294967295);
+        assert_eq!(part2(&schematic), 4294967295 * 4294967295);
+    }
+}
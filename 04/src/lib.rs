@@ -0,0 +1,398 @@
+//! Day 04: Scratchcards
+
+use std::{collections::HashSet, error::Error, fmt};
+
+use utilities::{
+    parse::{self, Cursor, InputError},
+    solution::{ParseError, Solution},
+};
+
+/// Structure representing a scratchcard.
+#[derive(Clone, Debug)]
+pub struct Scratchcard {
+    /// Number of the card
+    card_number: u32,
+    /// Winning numbers (left side) on the scratchcard.
+    winning_numbers: HashSet<u32>,
+    /// Numbers (right side) on the scratchcard.
+    numbers: HashSet<u32>,
+}
+
+impl Scratchcard {
+    /// Make a new scratchcard from a line of the input.
+    fn parse(mut line: Cursor) -> Result<Self, InputError> {
+        line.literal("Card")?;
+        let card_number = line.unsigned()?;
+        line.literal(":")?;
+        let winning_numbers = line.list(|c| c.unsigned())?.into_iter().collect();
+        line.literal("|")?;
+        let numbers = line.list(|c| c.unsigned())?.into_iter().collect();
+        line.end()?;
+        Ok(Self {
+            card_number,
+            winning_numbers,
+            numbers,
+        })
+    }
+
+    /// Get the number printed on the card.
+    pub fn card_number(&self) -> u32 {
+        self.card_number
+    }
+
+    /// Calculate points for matching numbers, or None if they don't fit in a
+    /// u64.
+    fn calculate_score(&self) -> Option<u64> {
+        match self.matching_numbers() {
+            0 => Some(0),
+            matches => u32::try_from(matches - 1)
+                .ok()
+                .and_then(|shift| 1u64.checked_shl(shift)),
+        }
+    }
+
+    /// Get number of matching numbers.
+    fn matching_numbers(&self) -> usize {
+        self.winning_numbers.intersection(&self.numbers).count()
+    }
+}
+
+/// Error working out how many copies of each card there are.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CascadeError {
+    /// Card at `position` wins copies of cards past the end of the table.
+    PastEnd {
+        position: usize,
+        matches: usize,
+        cards: usize,
+    },
+    /// Copies of the card at `position` don't fit in a u64.
+    Overflow { position: usize },
+    /// Points for the card at `position` don't fit in a u64.
+    ScoreOverflow { position: usize },
+}
+
+impl fmt::Display for CascadeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CascadeError::PastEnd {
+                position,
+                matches,
+                cards,
+            } => write!(
+                f,
+                "card {} has {matches} matches but there are only {cards} cards",
+                position + 1
+            ),
+            CascadeError::Overflow { position } => {
+                write!(f, "too many copies of card {}", position + 1)
+            }
+            CascadeError::ScoreOverflow { position } => {
+                write!(f, "too many points for card {}", position + 1)
+            }
+        }
+    }
+}
+
+impl Error for CascadeError {}
+
+impl CascadeError {
+    /// Position of the card the error is about.
+    pub fn position(&self) -> usize {
+        match self {
+            CascadeError::PastEnd { position, .. }
+            | CascadeError::Overflow { position }
+            | CascadeError::ScoreOverflow { position } => *position,
+        }
+    }
+}
+
+/// Count the copies of every card, given how many matches each card has in
+/// order. Each card wins one copy of the next `matches` cards for every copy of
+/// it, so the copies are pushed forward as a running total with the amounts
+/// that stop applying stored where they stop. Linear in the number of cards.
+pub fn cascade(matches: &[usize]) -> Result<Vec<u64>, CascadeError> {
+    let cards = matches.len();
+    let mut copies = vec![0; cards];
+    // Amount to take off the running total when reaching each position.
+    let mut expiring = vec![0u64; cards + 1];
+    let mut running: u64 = 0;
+    for (position, matches) in matches.iter().enumerate() {
+        let overflow = CascadeError::Overflow { position };
+        running -= expiring[position];
+        copies[position] = running.checked_add(1).ok_or(overflow.clone())?;
+
+        if *matches == 0 {
+            continue;
+        }
+        let end = position + matches + 1;
+        if end > cards {
+            return Err(CascadeError::PastEnd {
+                position,
+                matches: *matches,
+                cards,
+            });
+        }
+        running = running
+            .checked_add(copies[position])
+            .ok_or(overflow.clone())?;
+        expiring[end] = expiring[end]
+            .checked_add(copies[position])
+            .ok_or(overflow)?;
+    }
+    Ok(copies)
+}
+
+/// Every scratchcard along with how many of each there end up being.
+#[derive(Clone, Debug)]
+pub struct Pile {
+    cards: Vec<Scratchcard>,
+    /// Points each card is worth.
+    scores: Vec<u64>,
+    /// Copies of each card won, counting the original.
+    copies: Vec<u64>,
+}
+
+impl Pile {
+    /// Make a new pile, working out the points and copies of every card. Errors
+    /// if a card wins copies of cards past the end or there are too many points
+    /// or cards to count.
+    pub fn new(cards: Vec<Scratchcard>) -> Result<Self, CascadeError> {
+        let scores = cards
+            .iter()
+            .enumerate()
+            .map(|(position, card)| {
+                card.calculate_score()
+                    .ok_or(CascadeError::ScoreOverflow { position })
+            })
+            .collect::<Result<Vec<u64>, CascadeError>>()?;
+        // Make sure the total fits too, so part 1 can't overflow.
+        scores
+            .iter()
+            .enumerate()
+            .try_fold(0u64, |total, (position, score)| {
+                total
+                    .checked_add(*score)
+                    .ok_or(CascadeError::ScoreOverflow { position })
+            })?;
+
+        let matches: Vec<usize> = cards.iter().map(Scratchcard::matching_numbers).collect();
+        let copies = cascade(&matches)?;
+        // Make sure the total fits too, so part 2 can't overflow.
+        copies
+            .iter()
+            .enumerate()
+            .try_fold(0u64, |total, (position, copies)| {
+                total
+                    .checked_add(*copies)
+                    .ok_or(CascadeError::Overflow { position })
+            })?;
+        Ok(Self {
+            cards,
+            scores,
+            copies,
+        })
+    }
+
+    /// Get every card in order.
+    pub fn cards(&self) -> &[Scratchcard] {
+        &self.cards
+    }
+}
+
+/// Get points for matching cards.
+fn part1(pile: &Pile) -> u64 {
+    pile.scores.iter().sum()
+}
+
+/// Cards multiply by winning numbers. Get total count of cards.
+fn part2(pile: &Pile) -> u64 {
+    pile.copies.iter().sum()
+}
+
+/// Day 04 solution.
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Pile;
+    type Answer = u64;
+
+    const TITLE: &'static str = "Scratchcards";
+
+    fn parse(input: &str) -> Result<Pile, ParseError> {
+        let cards = parse::lines(input)
+            .map(Scratchcard::parse)
+            .collect::<Result<Vec<Scratchcard>, InputError>>()?;
+        // Cards are one to a line.
+        Ok(Pile::new(cards).map_err(|e| InputError {
+            line: e.position() + 1,
+            column: 1,
+            message: e.to_string(),
+        })?)
+    }
+
+    fn part1(input: &Pile) -> u64 {
+        part1(input)
+    }
+
+    fn part2(input: &Pile) -> u64 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utilities::solution::Part;
+
+    /// Make a scratchcard with the numbers.
+    fn card(winning_numbers: &[u32], numbers: &[u32]) -> Scratchcard {
+        Scratchcard {
+            card_number: 1,
+            winning_numbers: winning_numbers.iter().copied().collect(),
+            numbers: numbers.iter().copied().collect(),
+        }
+    }
+
+    #[test]
+    fn part1_example01() {
+        Day04::check_example(4, Part::One);
+    }
+
+    #[test]
+    #[ignore = "needs the puzzle input"]
+    fn test_part1() {
+        Day04::check_input(4, Part::One);
+    }
+
+    #[test]
+    fn part2_example01() {
+        Day04::check_example(4, Part::Two);
+    }
+
+    #[test]
+    #[ignore = "needs the puzzle input"]
+    fn test_part2() {
+        Day04::check_input(4, Part::Two);
+    }
+
+    #[test]
+    fn test_scratchcard_parse() {
+        let scratch = Scratchcard::parse(Cursor::new(
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+        ))
+        .unwrap();
+        let winning_nums = HashSet::from([41, 48, 83, 86, 17]);
+        let nums = HashSet::from([83, 86, 6, 31, 17, 9, 48, 53]);
+        assert_eq!(scratch.card_number(), 1);
+        assert_eq!(scratch.winning_numbers, winning_nums);
+        assert_eq!(scratch.numbers, nums);
+
+        let big = Scratchcard::parse(Cursor::new("Card 4000: 1 | 1")).unwrap();
+        assert_eq!(big.card_number(), 4000);
+
+        let error = Scratchcard::parse(Cursor::new("Card 2: 1 2 3 4")).unwrap_err();
+        assert_eq!(error.message, "expected '|'");
+    }
+
+    #[test]
+    fn test_scratchcard_score_matches() {
+        let scratch = card(&[41, 48, 83, 86, 17], &[83, 86, 6, 31, 17, 9, 48, 53]);
+        assert_eq!(scratch.matching_numbers(), 4);
+        assert_eq!(scratch.calculate_score(), Some(8));
+    }
+
+    #[test]
+    fn test_scratchcard_score_no_matches() {
+        let scratch = card(&[1, 2, 3, 4, 5], &[83, 86, 6, 31, 17, 9, 48, 53]);
+        assert_eq!(scratch.matching_numbers(), 0);
+        assert_eq!(scratch.calculate_score(), Some(0));
+    }
+
+    #[test]
+    fn test_parse_too_many_points() {
+        /// Input where the first `big` cards match every one of `matches`
+        /// numbers and the rest of the `cards` match none.
+        fn input(big: usize, matches: u32, cards: usize) -> String {
+            let numbers: Vec<String> = (1..=matches).map(|n| n.to_string()).collect();
+            let numbers = numbers.join(" ");
+            (1..=cards)
+                .map(|card| match card <= big {
+                    true => format!("Card {card}: {numbers} | {numbers}"),
+                    false => format!("Card {card}: 1 | 2"),
+                })
+                .collect::<Vec<String>>()
+                .join("\n")
+        }
+
+        let error = Day04::parse(&input(1, 70, 80)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: too many points for card 1"
+        );
+        // 64 matches are worth 2^63 points, which fits, but two cards of it don't.
+        let pile = Day04::parse(&input(1, 64, 65)).unwrap();
+        assert_eq!(pile.cards().len(), 65);
+        assert_eq!(part1(&pile), 1 << 63);
+        let error = Day04::parse(&input(2, 64, 66)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: too many points for card 2"
+        );
+    }
+
+    #[test]
+    fn test_cascade() {
+        assert_eq!(cascade(&[4, 2, 2, 1, 0, 0]), Ok(vec![1, 2, 4, 8, 14, 1]));
+        assert_eq!(cascade(&[]), Ok(vec![]));
+        assert_eq!(
+            cascade(&[1, 2, 0]),
+            Err(CascadeError::PastEnd {
+                position: 1,
+                matches: 2,
+                cards: 3
+            })
+        );
+        assert_eq!(
+            CascadeError::PastEnd {
+                position: 1,
+                matches: 2,
+                cards: 3
+            }
+            .to_string(),
+            "card 2 has 2 matches but there are only 3 cards"
+        );
+    }
+
+    #[test]
+    fn test_parse_card_past_end() {
+        let error = Day04::parse("Card 1: 1 2 | 1 3\nCard 2: 1 2 | 1 2").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: card 2 has 2 matches but there are only 2 cards"
+        );
+        let pile = Day04::parse("Card 1: 1 2 | 1 3\nCard 2: 1 2 | 3 4").unwrap();
+        assert_eq!(part2(&pile), 3);
+    }
+
+    #[test]
+    fn test_cascade_many_cards() {
+        // Every card wins a copy of the next one, so card n has n copies.
+        let cards = 5000;
+        let mut matches = vec![1; cards];
+        matches[cards - 1] = 0;
+        let copies = cascade(&matches).unwrap();
+        assert_eq!(copies[cards - 1], cards as u64);
+        assert_eq!(copies.iter().sum::<u64>(), (cards * (cards + 1) / 2) as u64);
+
+        // Every card wins a copy of the next two, so the copies grow like
+        // Fibonacci numbers and run out of room.
+        let mut matches = vec![2; 200];
+        matches[198] = 1;
+        matches[199] = 0;
+        assert!(matches!(
+            cascade(&matches),
+            Err(CascadeError::Overflow { .. })
+        ));
+    }
+}
@@ -0,0 +1,195 @@
+//! Day 05: If You Give A Seed A Fertilizer
+
+use utilities::{
+    interval::{Interval, IntervalSet, RangeMap},
+    parse::{self, Cursor, InputError},
+    solution::{ParseError, Solution},
+};
+
+/// Structure representing the almanac.
+#[derive(Debug)]
+pub struct Almanac {
+    /// Numbers from the seeds line.
+    seeds: Vec<i64>,
+    /// Every map from seed-to-soil through humidity-to-location, in order.
+    maps: Vec<RangeMap>,
+}
+
+impl Almanac {
+    /// Make a new almanac from the puzzle input.
+    fn new_from_string(string: &str) -> Result<Self, InputError> {
+        let sections = parse::sections(string);
+        let (seeds_section, map_sections) = sections
+            .split_first()
+            .ok_or_else(|| Cursor::new(string).error("missing seeds"))?;
+        let mut seeds_line = *seeds_section;
+        seeds_line.literal("seeds:")?;
+        let seeds = seeds_line
+            .ints()
+            .collect::<Result<Vec<i64>, InputError>>()?;
+        if seeds.is_empty() {
+            return Err(seeds_line.error("expected seeds"));
+        }
+        if seeds.len() % 2 != 0 {
+            return Err(seeds_line.error(format!(
+                "expected seeds in pairs, found {} numbers",
+                seeds.len()
+            )));
+        }
+        if let Some(pair) = seeds.chunks(2).find(|pair| pair[1] <= 0) {
+            return Err(seeds_line.error(format!(
+                "seed range starting at {} has length {}, expected at least 1",
+                pair[0], pair[1]
+            )));
+        }
+
+        let mut maps: Vec<RangeMap> = Vec::new();
+        for section in map_sections {
+            let mut lines = section.lines();
+            let header = lines.next().expect("Sections aren't empty.");
+            if !header.rest().ends_with("map:") {
+                return Err(header.error("expected a map header"));
+            }
+            let mut map = RangeMap::new();
+            for line in lines {
+                let [dest, source, len] = line.ints_n()?;
+                if len < 0 {
+                    return Err(line.error(format!("rule has negative length {len}")));
+                }
+                map.try_add_rule(dest, source, len).map_err(|existing| {
+                    line.error(format!(
+                        "rule for {} overlaps the rule for {existing}",
+                        Interval::from_len(source, len)
+                    ))
+                })?;
+            }
+            maps.push(map);
+        }
+        Ok(Self { seeds, maps })
+    }
+
+    /// One map that converts seeds straight to locations.
+    fn seed_to_location(&self) -> RangeMap {
+        RangeMap::compose(&self.maps)
+    }
+}
+
+/// Convert a seed number to a location number using all the conversions between.
+fn part1(almanac: &Almanac) -> i64 {
+    let map = almanac.seed_to_location();
+    almanac
+        .seeds
+        .iter()
+        .map(|seed| map.map(*seed))
+        .min()
+        .expect("Parsing checks there are seeds.")
+}
+
+/// Seeds come in (start, length) pairs. Map the whole ranges to find the lowest
+/// location. Parsing checks every length is at least one.
+fn part2(almanac: &Almanac) -> i64 {
+    let seeds: IntervalSet = almanac
+        .seeds
+        .chunks(2)
+        .map(|pair| Interval::from_len(pair[0], pair[1]))
+        .collect();
+    almanac
+        .seed_to_location()
+        .map_set(&seeds)
+        .min()
+        .expect("Parsing checks there are seeds.")
+}
+
+/// Day 05 solution.
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Almanac;
+    type Answer = i64;
+
+    const TITLE: &'static str = "If You Give A Seed A Fertilizer";
+
+    fn parse(input: &str) -> Result<Almanac, ParseError> {
+        Ok(Almanac::new_from_string(input)?)
+    }
+
+    fn part1(input: &Almanac) -> i64 {
+        part1(input)
+    }
+
+    fn part2(input: &Almanac) -> i64 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utilities::solution::Part;
+
+    #[test]
+    fn part1_example01() {
+        Day05::check_example(5, Part::One);
+    }
+
+    #[test]
+    fn part2_example01() {
+        Day05::check_example(5, Part::Two);
+    }
+
+    #[test]
+    fn test_almanac_new_from_string() {
+        let contents = std::fs::read_to_string("example.txt").unwrap();
+        let almanac = Almanac::new_from_string(&contents).unwrap();
+        assert_eq!(almanac.seeds, vec![79, 14, 55, 13]);
+        assert_eq!(almanac.maps.len(), 7);
+        assert_eq!(almanac.maps[0].map(79), 81);
+        assert_eq!(almanac.seed_to_location().map(79), 82);
+    }
+
+    #[test]
+    fn test_almanac_bad_map_line() {
+        let error = Almanac::new_from_string("seeds: 1 2\n\nseed-to-soil map:\n1 2").unwrap_err();
+        assert_eq!(error.line, 4);
+        assert!(Almanac::new_from_string("seeds 1 2").is_err());
+    }
+
+    #[test]
+    fn test_almanac_bad_seeds_and_rules() {
+        let error = Almanac::new_from_string("seeds:\n\nseed-to-soil map:\n1 2 3").unwrap_err();
+        assert_eq!((error.line, error.message.as_str()), (1, "expected seeds"));
+        let error = Day05::parse("seeds: 1 2 3\n\nseed-to-soil map:\n1 2 3").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 7: expected seeds in pairs, found 3 numbers"
+        );
+        let error = Almanac::new_from_string("seeds: 1 2\n\nseed-to-soil map:\n0 10 5\n100 12 5")
+            .unwrap_err();
+        assert_eq!(error.line, 5);
+        assert_eq!(
+            error.message,
+            "rule for [12, 17) overlaps the rule for [10, 15)"
+        );
+    }
+
+    #[test]
+    fn test_almanac_bad_lengths() {
+        let error = Day05::parse("seeds: 1 2 5 0\n\nseed-to-soil map:\n1 2 3").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 7: seed range starting at 5 has length 0, expected at least 1"
+        );
+        let error =
+            Almanac::new_from_string("seeds: 5 -2\n\nseed-to-soil map:\n1 2 3").unwrap_err();
+        assert_eq!(
+            error.message,
+            "seed range starting at 5 has length -2, expected at least 1"
+        );
+        let error = Almanac::new_from_string("seeds: 1 2\n\nseed-to-soil map:\n0 10 5\n1 2 -3")
+            .unwrap_err();
+        assert_eq!(
+            (error.line, error.message.as_str()),
+            (5, "rule has negative length -3")
+        );
+    }
+}
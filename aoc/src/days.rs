@@ -0,0 +1,18 @@
+//! Registry of every solved day. `aoc new <day>` adds new days here so the runner
+//! can find them.
+
+use utilities::solution::Day;
+
+/// Every registered day in order.
+pub const DAYS: &[Day] = &[
+    Day::new::<day01::Day01>(1),
+    Day::new::<day02::Day02>(2),
+    Day::new::<day03::Day03>(3),
+    Day::new::<day04::Day04>(4),
+    Day::new::<day05::Day05>(5),
+];
+
+/// Find a registered day by its number.
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
@@ -0,0 +1,472 @@
+//! Runner for every day's solution.
+//!
+//! ```text
+//! aoc run <day|all> [--part <1|2>] [--input <path>] [--bench <runs>] [--format <table|json>]
+//! aoc submit <day> <1|2> [--answer <answer>]
+//! aoc new <day> [--title <title>]
+//! aoc examples <day> <saved puzzle page>
+//! ```
+
+mod days;
+mod report;
+mod scaffold;
+mod stats;
+
+use std::{
+    path::Path,
+    process::ExitCode,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use report::Report;
+use utilities::{
+    answers::{self, Ledger},
+    input::{self, Inputs, YEAR},
+    solution::{Day, Part},
+};
+
+const USAGE: &str = "Usage:
+  aoc run <day|all> [--part <1|2>] [--input <path>] [--bench <runs>] [--format <table|json>]
+  aoc submit <day> <1|2> [--answer <answer>]
+  aoc new <day> [--title <title>]
+  aoc examples <day> <saved puzzle page>";
+
+/// How to print the results.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Format {
+    #[default]
+    Table,
+    Json,
+}
+
+/// Which days to run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Which {
+    All,
+    Day(u8),
+}
+
+/// Command parsed from the arguments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Command {
+    Run {
+        which: Which,
+        part: Option<Part>,
+        input: Option<String>,
+        /// Number of times to run each day for benchmarking.
+        bench: Option<usize>,
+        format: Format,
+    },
+    Submit {
+        day: u8,
+        part: Part,
+        /// Answer to submit instead of solving the real input.
+        answer: Option<String>,
+    },
+    New {
+        day: u8,
+        title: Option<String>,
+    },
+    Examples {
+        day: u8,
+        /// Puzzle page saved as HTML.
+        page: String,
+    },
+}
+
+/// Parse a part number argument.
+fn parse_part(value: &str) -> Result<Part, String> {
+    value
+        .parse()
+        .ok()
+        .and_then(Part::from_number)
+        .ok_or_else(|| format!("Part must be 1 or 2, not '{value}'."))
+}
+
+/// Parse the command line arguments, not including the program name.
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut args = args.iter();
+    match args.next().map(String::as_str) {
+        Some("run") => parse_run(args),
+        Some("submit") => parse_submit(args),
+        Some("new") => parse_new(args),
+        Some("examples") => parse_examples(args),
+        Some(other) => Err(format!("Unknown command '{other}'.")),
+        None => Err("Missing command.".to_string()),
+    }
+}
+
+/// Parse the arguments of the submit command.
+fn parse_submit(mut args: std::slice::Iter<String>) -> Result<Command, String> {
+    let day = args.next().ok_or("Missing day.")?;
+    let day = day
+        .parse()
+        .map_err(|_| format!("'{day}' is not a day number."))?;
+    let part = parse_part(args.next().ok_or("Missing part.")?)?;
+
+    let mut answer: Option<String> = None;
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {flag}."))?;
+        match flag.as_str() {
+            "--answer" => answer = Some(value.clone()),
+            _ => return Err(format!("Unknown option '{flag}'.")),
+        }
+    }
+    Ok(Command::Submit { day, part, answer })
+}
+
+/// Parse the arguments of the new command.
+fn parse_new(mut args: std::slice::Iter<String>) -> Result<Command, String> {
+    let day = args.next().ok_or("Missing day.")?;
+    let day = day
+        .parse()
+        .map_err(|_| format!("'{day}' is not a day number."))?;
+
+    let mut title: Option<String> = None;
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {flag}."))?;
+        match flag.as_str() {
+            "--title" => title = Some(value.clone()),
+            _ => return Err(format!("Unknown option '{flag}'.")),
+        }
+    }
+    Ok(Command::New { day, title })
+}
+
+/// Parse the arguments of the examples command.
+fn parse_examples(mut args: std::slice::Iter<String>) -> Result<Command, String> {
+    let day = args.next().ok_or("Missing day.")?;
+    let day = day
+        .parse()
+        .map_err(|_| format!("'{day}' is not a day number."))?;
+    let page = args.next().ok_or("Missing puzzle page.")?.clone();
+    if let Some(extra) = args.next() {
+        return Err(format!("Unexpected argument '{extra}'."));
+    }
+    Ok(Command::Examples { day, page })
+}
+
+/// Parse the arguments of the run command.
+fn parse_run(mut args: std::slice::Iter<String>) -> Result<Command, String> {
+    let which = match args.next().map(String::as_str) {
+        Some("all") => Which::All,
+        Some(day) => Which::Day(
+            day.parse()
+                .map_err(|_| format!("'{day}' is not a day number."))?,
+        ),
+        None => return Err("Missing day.".to_string()),
+    };
+
+    let mut part: Option<Part> = None;
+    let mut input: Option<String> = None;
+    let mut bench: Option<usize> = None;
+    let mut format = Format::default();
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {flag}."))?;
+        match flag.as_str() {
+            "--part" => part = Some(parse_part(value)?),
+            "--input" => input = Some(value.clone()),
+            "--bench" => {
+                bench = Some(value.parse().ok().filter(|runs| *runs > 0).ok_or_else(|| {
+                    format!("Bench runs must be a positive number, not '{value}'.")
+                })?)
+            }
+            "--format" => {
+                format = match value.as_str() {
+                    "table" => Format::Table,
+                    "json" => Format::Json,
+                    _ => return Err(format!("Format must be table or json, not '{value}'.")),
+                }
+            }
+            _ => return Err(format!("Unknown option '{flag}'.")),
+        }
+    }
+
+    if which == Which::All && input.is_some() {
+        return Err("--input can only be used with a single day.".to_string());
+    }
+    Ok(Command::Run {
+        which,
+        part,
+        input,
+        bench,
+        format,
+    })
+}
+
+/// Read the input file if one was given, otherwise get the day's input from
+/// the input cache.
+fn load_input(day: &Day, file: Option<&str>) -> Result<String, String> {
+    match file {
+        Some(file) => {
+            std::fs::read_to_string(file).map_err(|e| format!("Couldn't open {file}: {e}"))
+        }
+        None => input::load(day.number)
+            .map_err(|e| format!("Couldn't get the input for day {}: {e}", day.number)),
+    }
+}
+
+/// Load the answers ledger.
+fn load_ledger() -> Result<Ledger, String> {
+    let path = Ledger::default_path();
+    Ledger::load(&path).map_err(|e| format!("Couldn't read {}: {e}", path.display()))
+}
+
+/// Run one day or all of them and print the results. Answers for the real
+/// inputs are checked against the ledger.
+fn run(
+    which: Which,
+    part: Option<Part>,
+    input: Option<String>,
+    bench: Option<usize>,
+    format: Format,
+) -> Result<(), String> {
+    let check = input.is_none();
+    let parts: Vec<Part> = match part {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    };
+    let runs = bench.unwrap_or(1);
+
+    let mut reports: Vec<Report> = match which {
+        Which::Day(number) => {
+            let day = days::find(number).ok_or_else(|| format!("Day {number} isn't solved."))?;
+            let report = report::measure(day, load_input(day, input.as_deref()), &parts, runs);
+            if let Err(e) = &report.outcome {
+                return Err(e.clone());
+            }
+            vec![report]
+        }
+        Which::All => days::DAYS
+            .iter()
+            .map(|day| report::measure(day, load_input(day, None), &parts, runs))
+            .collect(),
+    };
+
+    if check {
+        let ledger = load_ledger()?;
+        for report in &mut reports {
+            report.check(&ledger);
+        }
+    }
+
+    let output = match (format, bench) {
+        (Format::Json, _) => report::json(&reports),
+        (Format::Table, None) => report::results_table(&reports, &parts),
+        (Format::Table, Some(_)) => report::bench_table(&reports),
+    };
+    println!("{output}");
+    if reports.iter().any(Report::has_incorrect) {
+        return Err("Some answers don't match the ledger.".to_string());
+    }
+    Ok(())
+}
+
+/// Submit an answer for one part, solving the real input if no answer is given.
+fn submit(number: u8, part: Part, answer: Option<String>) -> Result<(), String> {
+    let day = days::find(number).ok_or_else(|| format!("Day {number} isn't solved."))?;
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let input = load_input(day, None)?;
+            let result = day
+                .run(&input, &[part])
+                .map_err(|e| format!("Couldn't parse input: {e}"))?;
+            result
+                .parts
+                .into_iter()
+                .next()
+                .expect("One part was run.")
+                .answer
+        }
+    };
+
+    let inputs = Inputs::from_env().map_err(|e| e.to_string())?;
+    let path = Ledger::default_path();
+    let mut ledger = load_ledger()?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Clock is after 1970.")
+        .as_secs();
+    let submission = answers::submit(&inputs, &mut ledger, (YEAR, number, part), &answer, now)
+        .map_err(|e| e.to_string())?;
+    ledger
+        .save(&path)
+        .map_err(|e| format!("Couldn't write {}: {e}", path.display()))?;
+    println!("Day {number} part {part}: {answer}\n{submission}");
+    Ok(())
+}
+
+/// Root of the workspace.
+fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("aoc is inside the workspace")
+}
+
+/// Make a new day's crate and register it.
+fn new_day(day: u8, title: Option<String>) -> Result<(), String> {
+    let root = workspace_root();
+    let title = title.unwrap_or_else(|| format!("Day {day:02}"));
+    for path in scaffold::new_day(root, day, &title)? {
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+/// Save the examples from a saved puzzle page and their answers in the ledger.
+fn examples(day: u8, page: &str) -> Result<(), String> {
+    let html = std::fs::read_to_string(page).map_err(|e| format!("Couldn't open {page}: {e}"))?;
+    let path = Ledger::default_path();
+    let mut ledger = load_ledger()?;
+    for line in scaffold::save_examples(workspace_root(), day, &html, &mut ledger)? {
+        println!("{line}");
+    }
+    ledger
+        .save(&path)
+        .map_err(|e| format!("Couldn't write {}: {e}", path.display()))
+}
+
+/// Run the command.
+fn execute(command: Command) -> Result<(), String> {
+    match command {
+        Command::Run {
+            which,
+            part,
+            input,
+            bench,
+            format,
+        } => run(which, part, input, bench, format),
+        Command::Submit { day, part, answer } => submit(day, part, answer),
+        Command::New { day, title } => new_day(day, title),
+        Command::Examples { day, page } => examples(day, &page),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{e}\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match execute(command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            parse_args(&args("run 3 --part 2 --input path.txt")),
+            Ok(Command::Run {
+                which: Which::Day(3),
+                part: Some(Part::Two),
+                input: Some("path.txt".to_string()),
+                bench: None,
+                format: Format::Table,
+            })
+        );
+        assert_eq!(
+            parse_args(&args("run all")),
+            Ok(Command::Run {
+                which: Which::All,
+                part: None,
+                input: None,
+                bench: None,
+                format: Format::Table,
+            })
+        );
+        assert_eq!(
+            parse_args(&args("run all --bench 10 --format json")),
+            Ok(Command::Run {
+                which: Which::All,
+                part: None,
+                input: None,
+                bench: Some(10),
+                format: Format::Json,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_submit_args() {
+        assert_eq!(
+            parse_args(&args("submit 5 2")),
+            Ok(Command::Submit {
+                day: 5,
+                part: Part::Two,
+                answer: None,
+            })
+        );
+        assert_eq!(
+            parse_args(&args("submit 5 1 --answer 35")),
+            Ok(Command::Submit {
+                day: 5,
+                part: Part::One,
+                answer: Some("35".to_string()),
+            })
+        );
+        assert!(parse_args(&args("submit 5")).is_err());
+        assert!(parse_args(&args("submit 5 3")).is_err());
+        assert!(parse_args(&args("submit 5 1 --part 2")).is_err());
+        assert_eq!(
+            parse_args(&args("new 6 --title Boats")),
+            Ok(Command::New {
+                day: 6,
+                title: Some("Boats".to_string()),
+            })
+        );
+        assert!(parse_args(&args("new")).is_err());
+        assert_eq!(
+            parse_args(&args("examples 6 day06.html")),
+            Ok(Command::Examples {
+                day: 6,
+                page: "day06.html".to_string(),
+            })
+        );
+        assert!(parse_args(&args("examples 6")).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert!(parse_args(&args("")).is_err());
+        assert!(parse_args(&args("walk 3")).is_err());
+        assert!(parse_args(&args("run three")).is_err());
+        assert!(parse_args(&args("run 3 --part 3")).is_err());
+        assert!(parse_args(&args("run 3 --part")).is_err());
+        assert!(parse_args(&args("run all --input path.txt")).is_err());
+        assert!(parse_args(&args("run all --bench 0")).is_err());
+        assert!(parse_args(&args("run all --format xml")).is_err());
+    }
+
+    #[test]
+    fn test_days_registered_in_order() {
+        let numbers: Vec<u8> = days::DAYS.iter().map(|day| day.number).collect();
+        // `aoc new` adds days, so only check they're unique and in order.
+        assert!(!numbers.is_empty());
+        assert!(numbers.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(days::find(3).map(|day| day.title), Some("Gear Ratios"));
+        assert!(days::find(25).is_none());
+    }
+}
@@ -0,0 +1,412 @@
+//! Running days, collecting their answers and timings, and printing them as a
+//! table or JSON.
+
+use std::time::Duration;
+
+use utilities::{
+    answers::{Ledger, Verdict},
+    input::YEAR,
+    solution::{Day, Part},
+};
+
+use crate::stats::Stats;
+
+/// A step of solving a day that gets timed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    Parse,
+    Part(Part),
+}
+
+impl Stage {
+    /// Name used in tables and JSON.
+    fn name(&self) -> &'static str {
+        match self {
+            Stage::Parse => "parse",
+            Stage::Part(Part::One) => "part1",
+            Stage::Part(Part::Two) => "part2",
+        }
+    }
+}
+
+/// Answer and timings for one stage.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StageReport {
+    pub stage: Stage,
+    /// Answer from the first run. Parsing has no answer.
+    pub answer: Option<String>,
+    /// What the answers ledger says about the answer, if it was checked.
+    pub verdict: Option<Verdict>,
+    pub stats: Stats,
+}
+
+impl StageReport {
+    /// Answer with a mark showing what the ledger says about it.
+    fn marked_answer(&self) -> Option<String> {
+        let answer = self.answer.as_ref()?;
+        Some(match self.verdict {
+            Some(Verdict::Correct) => format!("{answer} ✓"),
+            Some(Verdict::Incorrect) => format!("{answer} ✗"),
+            Some(Verdict::Unknown) => format!("{answer} ?"),
+            None => answer.clone(),
+        })
+    }
+}
+
+/// Everything from running one day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    pub number: u8,
+    pub title: &'static str,
+    /// How many times the day was run.
+    pub runs: usize,
+    /// Reports for parsing then each part, or why the day couldn't run.
+    pub outcome: Result<Vec<StageReport>, String>,
+}
+
+/// Run the day `runs` times on the input, or report why there isn't one.
+pub fn measure(day: &Day, input: Result<String, String>, parts: &[Part], runs: usize) -> Report {
+    Report {
+        number: day.number,
+        title: day.title,
+        runs,
+        outcome: input.and_then(|input| measure_stages(day, &input, parts, runs)),
+    }
+}
+
+impl Report {
+    /// Check every answer against the ledger.
+    pub fn check(&mut self, ledger: &Ledger) {
+        if let Ok(stages) = &mut self.outcome {
+            for stage in stages {
+                if let (Stage::Part(part), Some(answer)) = (stage.stage, &stage.answer) {
+                    stage.verdict = Some(ledger.check(YEAR, self.number, part, answer));
+                }
+            }
+        }
+    }
+
+    /// Whether any answer is known to be wrong.
+    pub fn has_incorrect(&self) -> bool {
+        self.outcome.as_ref().is_ok_and(|stages| {
+            stages
+                .iter()
+                .any(|stage| stage.verdict == Some(Verdict::Incorrect))
+        })
+    }
+}
+
+/// Collect the timings of every stage over all the runs.
+fn measure_stages(
+    day: &Day,
+    input: &str,
+    parts: &[Part],
+    runs: usize,
+) -> Result<Vec<StageReport>, String> {
+    let mut answers: Vec<String> = Vec::new();
+    let mut parse_times: Vec<Duration> = Vec::new();
+    let mut part_times: Vec<Vec<Duration>> = vec![Vec::new(); parts.len()];
+    for _ in 0..runs.max(1) {
+        let result = day
+            .run(input, parts)
+            .map_err(|e| format!("Couldn't parse input: {e}"))?;
+        parse_times.push(result.parse_time);
+        for (times, part) in part_times.iter_mut().zip(&result.parts) {
+            times.push(part.time);
+        }
+        if answers.is_empty() {
+            answers = result.parts.into_iter().map(|part| part.answer).collect();
+        }
+    }
+
+    let mut stages = vec![StageReport {
+        stage: Stage::Parse,
+        answer: None,
+        verdict: None,
+        stats: Stats::new(&parse_times).expect("At least one run."),
+    }];
+    for ((part, answer), times) in parts.iter().zip(answers).zip(part_times) {
+        stages.push(StageReport {
+            stage: Stage::Part(*part),
+            answer: Some(answer),
+            verdict: None,
+            stats: Stats::new(&times).expect("At least one run."),
+        });
+    }
+    Ok(stages)
+}
+
+/// Format a duration short enough for a table.
+fn duration(time: Duration) -> String {
+    format!("{time:.2?}")
+}
+
+/// Format rows into a table with aligned columns and a header separator.
+pub fn table(header: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = header.iter().map(|h| h.len()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |cells: Vec<&str>| -> String {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        padded.join(" | ").trim_end().to_string()
+    };
+
+    let mut lines = vec![format_row(header.to_vec())];
+    lines.push(
+        widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<String>>()
+            .join("-+-"),
+    );
+    for row in rows {
+        lines.push(format_row(row.iter().map(String::as_str).collect()));
+    }
+    lines.join("\n")
+}
+
+/// Table with one row per day showing the answers and how long each stage took.
+pub fn results_table(reports: &[Report], parts: &[Part]) -> String {
+    let mut header = vec!["Day", "Title"];
+    for part in parts {
+        header.push(match part {
+            Part::One => "Part 1",
+            Part::Two => "Part 2",
+        });
+    }
+    header.push("Parse");
+    for part in parts {
+        header.push(match part {
+            Part::One => "Part 1 time",
+            Part::Two => "Part 2 time",
+        });
+    }
+
+    let rows: Vec<Vec<String>> = reports
+        .iter()
+        .map(|report| {
+            let mut row = vec![format!("{:02}", report.number), report.title.to_string()];
+            match &report.outcome {
+                Ok(stages) => {
+                    row.extend(stages.iter().filter_map(StageReport::marked_answer));
+                    row.extend(stages.iter().map(|stage| duration(stage.stats.mean)));
+                }
+                Err(e) => row.push(e.clone()),
+            }
+            row
+        })
+        .collect();
+    table(&header, &rows)
+}
+
+/// Table with one row per stage showing the benchmark statistics.
+pub fn bench_table(reports: &[Report]) -> String {
+    let header = [
+        "Day", "Stage", "Answer", "Runs", "Min", "Median", "Mean", "Std dev",
+    ];
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for report in reports {
+        let day = format!("{:02}", report.number);
+        match &report.outcome {
+            Ok(stages) => {
+                for stage in stages {
+                    rows.push(vec![
+                        day.clone(),
+                        stage.stage.name().to_string(),
+                        stage.marked_answer().unwrap_or_default(),
+                        report.runs.to_string(),
+                        duration(stage.stats.min),
+                        duration(stage.stats.median),
+                        duration(stage.stats.mean),
+                        duration(stage.stats.stddev),
+                    ]);
+                }
+            }
+            Err(e) => rows.push(vec![day, "error".to_string(), e.clone()]),
+        }
+    }
+    table(&header, &rows)
+}
+
+/// Quote and escape a string for JSON.
+fn json_string(text: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Reports as a JSON array, one object per day. Times are in nanoseconds.
+pub fn json(reports: &[Report]) -> String {
+    let days: Vec<String> = reports
+        .iter()
+        .map(|report| {
+            let mut fields = vec![
+                format!("\"day\":{}", report.number),
+                format!("\"title\":{}", json_string(report.title)),
+                format!("\"runs\":{}", report.runs),
+            ];
+            match &report.outcome {
+                Ok(stages) => {
+                    let stages: Vec<String> = stages
+                        .iter()
+                        .map(|stage| {
+                            let answer = stage
+                                .answer
+                                .as_deref()
+                                .map_or("null".to_string(), json_string);
+                            let verdict = stage
+                                .verdict
+                                .map_or("null".to_string(), |v| json_string(&v.to_string()));
+                            format!(
+                                "{{\"stage\":\"{}\",\"answer\":{},\"verdict\":{},\"min_ns\":{},\"median_ns\":{},\"mean_ns\":{},\"stddev_ns\":{}}}",
+                                stage.stage.name(),
+                                answer,
+                                verdict,
+                                stage.stats.min.as_nanos(),
+                                stage.stats.median.as_nanos(),
+                                stage.stats.mean.as_nanos(),
+                                stage.stats.stddev.as_nanos(),
+                            )
+                        })
+                        .collect();
+                    fields.push(format!("\"stages\":[{}]", stages.join(",")));
+                }
+                Err(e) => fields.push(format!("\"error\":{}", json_string(e))),
+            }
+            format!("{{{}}}", fields.join(","))
+        })
+        .collect();
+    format!("[{}]", days.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> Report {
+        let stats = |micros: u64| Stats {
+            min: Duration::from_micros(micros),
+            median: Duration::from_micros(micros),
+            mean: Duration::from_micros(micros),
+            stddev: Duration::ZERO,
+        };
+        Report {
+            number: 1,
+            title: "Trebuchet?!",
+            runs: 1,
+            outcome: Ok(vec![
+                StageReport {
+                    stage: Stage::Parse,
+                    answer: None,
+                    verdict: None,
+                    stats: stats(1),
+                },
+                StageReport {
+                    stage: Stage::Part(Part::One),
+                    answer: Some("142".to_string()),
+                    verdict: None,
+                    stats: stats(2),
+                },
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_table() {
+        let rows = vec![
+            vec![
+                "01".to_string(),
+                "Long title".to_string(),
+                "142".to_string(),
+            ],
+            vec!["02".to_string(), "Short".to_string(), "8".to_string()],
+        ];
+        assert_eq!(
+            table(&["Day", "Title", "Part 1"], &rows),
+            "Day | Title      | Part 1\n\
+             ----+------------+-------\n\
+             01  | Long title | 142\n\
+             02  | Short      | 8"
+        );
+    }
+
+    #[test]
+    fn test_results_table() {
+        let table = results_table(&[report()], &[Part::One]);
+        assert_eq!(
+            table.lines().last(),
+            Some("01  | Trebuchet?! | 142    | 1.00µs | 2.00µs")
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let mut ledger = Ledger::default();
+        let mut report = report();
+        report.check(&ledger);
+        assert!(!report.has_incorrect());
+        assert_eq!(
+            results_table(&[report.clone()], &[Part::One])
+                .lines()
+                .last(),
+            Some("01  | Trebuchet?! | 142 ?  | 1.00µs | 2.00µs")
+        );
+
+        ledger.record(YEAR, 1, Part::One, "141", true);
+        report.check(&ledger);
+        assert!(report.has_incorrect());
+        assert!(json(&[report]).contains("\"verdict\":\"incorrect\""));
+    }
+
+    #[test]
+    fn test_json() {
+        let error = Report {
+            number: 2,
+            title: "Cube Conundrum",
+            runs: 3,
+            outcome: Err("Couldn't open \"02/input.txt\"".to_string()),
+        };
+        assert_eq!(
+            json(&[report(), error]),
+            "[{\"day\":1,\"title\":\"Trebuchet?!\",\"runs\":1,\"stages\":[\
+             {\"stage\":\"parse\",\"answer\":null,\"verdict\":null,\"min_ns\":1000,\"median_ns\":1000,\"mean_ns\":1000,\"stddev_ns\":0},\
+             {\"stage\":\"part1\",\"answer\":\"142\",\"verdict\":null,\"min_ns\":2000,\"median_ns\":2000,\"mean_ns\":2000,\"stddev_ns\":0}]},\
+             {\"day\":2,\"title\":\"Cube Conundrum\",\"runs\":3,\"error\":\"Couldn't open \\\"02/input.txt\\\"\"}]"
+        );
+    }
+
+    #[test]
+    fn test_measure() {
+        let day = crate::days::find(1).unwrap();
+        let input = std::fs::read_to_string("../01/example.txt").map_err(|e| e.to_string());
+        let report = measure(day, input, &[Part::One], 3);
+        assert_eq!(report.runs, 3);
+        let stages = report.outcome.unwrap();
+        assert_eq!(stages.len(), 2);
+        assert_eq!(stages[1].answer.as_deref(), Some("142"));
+        let missing = measure(day, Err("no input".to_string()), &[Part::One], 1);
+        assert_eq!(missing.outcome, Err("no input".to_string()));
+        let day02 = crate::days::find(2).unwrap();
+        let bad = measure(day02, Ok("Game x".to_string()), &[Part::One], 1);
+        assert!(bad.outcome.is_err());
+    }
+}
@@ -0,0 +1,378 @@
+//! Making the crate for a new day and registering it with the workspace and the
+//! runner, and saving the examples from its puzzle page.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use utilities::{answers::Ledger, examples::parse_page, input::YEAR, solution::Part};
+
+/// Text of a new day's `Cargo.toml`.
+fn manifest_template(day: u8) -> String {
+    format!(
+        "[package]
+name = \"day{day:02}\"
+version = \"0.1.0\"
+edition = \"2024\"
+
+[dependencies]
+utilities = {{ path = \"../utilities\" }}
+"
+    )
+}
+
+/// Text of a new day's `src/lib.rs` with stubs for parsing and both parts.
+fn lib_template(day: u8, title: &str) -> String {
+    let title_literal = format!("{title:?}");
+    format!(
+        "//! Day {day:02}: {title}
+
+use utilities::solution::{{ParseError, Solution}};
+
+/// Solve part 1. Not solved yet, 0 is a placeholder.
+fn part1(_lines: &[String]) -> u64 {{
+    0
+}}
+
+/// Solve part 2. Not solved yet, 0 is a placeholder.
+fn part2(_lines: &[String]) -> u64 {{
+    0
+}}
+
+/// Day {day:02} solution.
+pub struct Day{day:02};
+
+impl Solution for Day{day:02} {{
+    type Input = Vec<String>;
+    type Answer = u64;
+
+    const TITLE: &'static str = {title_literal};
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {{
+        Ok(input.lines().map(String::from).collect())
+    }}
+
+    fn part1(input: &Vec<String>) -> u64 {{
+        part1(input)
+    }}
+
+    fn part2(input: &Vec<String>) -> u64 {{
+        part2(input)
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use utilities::solution::Part;
+
+    #[test]
+    #[ignore = \"save the examples with aoc examples\"]
+    fn part1_example01() {{
+        Day{day:02}::check_example({day}, Part::One);
+    }}
+
+    #[test]
+    #[ignore = \"needs the puzzle input\"]
+    fn test_part1() {{
+        Day{day:02}::check_input({day}, Part::One);
+    }}
+
+    #[test]
+    #[ignore = \"save the examples with aoc examples\"]
+    fn part2_example01() {{
+        Day{day:02}::check_example({day}, Part::Two);
+    }}
+
+    #[test]
+    #[ignore = \"needs the puzzle input\"]
+    fn test_part2() {{
+        Day{day:02}::check_input({day}, Part::Two);
+    }}
+}}
+"
+    )
+}
+
+/// Insert `line` among the lines starting with `prefix`, keeping them sorted.
+/// The new line goes after the last of them if it sorts after all of them.
+fn insert_sorted(text: &str, prefix: &str, line: &str) -> Result<String, String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let entries: Vec<usize> = (0..lines.len())
+        .filter(|i| lines[*i].starts_with(prefix))
+        .collect();
+    let last = *entries
+        .last()
+        .ok_or_else(|| format!("Couldn't find any lines starting with '{prefix}'."))?;
+    let index = entries
+        .iter()
+        .copied()
+        .find(|i| lines[*i] > line)
+        .unwrap_or(last + 1);
+    lines.insert(index, line);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Add a day's directory to the workspace members, keeping the days in order.
+fn add_member(manifest: &str, day: u8) -> Result<String, String> {
+    let (before, rest) = manifest
+        .split_once("members = [")
+        .ok_or("Couldn't find the workspace members.")?;
+    let (members, after) = rest
+        .split_once(']')
+        .ok_or("Couldn't find the end of the workspace members.")?;
+    let mut members: Vec<String> = members
+        .split(',')
+        .map(|member| member.trim().to_string())
+        .filter(|member| !member.is_empty())
+        .collect();
+    members.push(format!("\"{day:02}\""));
+    let (mut days, mut others): (Vec<String>, Vec<String>) = members
+        .into_iter()
+        .partition(|member| member.trim_matches('"').parse::<u8>().is_ok());
+    days.sort();
+    others.extend(days);
+    Ok(format!("{before}members = [{}]{after}", others.join(", ")))
+}
+
+/// Make the crate for a new day in the workspace at `root` and register it.
+/// Returns every file created or changed.
+pub fn new_day(root: &Path, day: u8, title: &str) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("Day must be 1 to 25, not {day}."));
+    }
+    let dir = root.join(format!("{day:02}"));
+    if dir.exists() {
+        return Err(format!("{} already exists.", dir.display()));
+    }
+
+    let read = |path: &Path| {
+        fs::read_to_string(path).map_err(|e| format!("Couldn't read {}: {e}", path.display()))
+    };
+    let workspace = root.join("Cargo.toml");
+    let runner = root.join("aoc").join("Cargo.toml");
+    let registry = root.join("aoc").join("src").join("days.rs");
+    // Work out every edit before writing anything so a failure leaves the
+    // workspace as it was.
+    let edits = [
+        (workspace.clone(), add_member(&read(&workspace)?, day)?),
+        (
+            runner.clone(),
+            insert_sorted(
+                &read(&runner)?,
+                "day",
+                &format!("day{day:02} = {{ path = \"../{day:02}\" }}"),
+            )?,
+        ),
+        (
+            registry.clone(),
+            insert_sorted(
+                &read(&registry)?,
+                "    Day::new::<day",
+                &format!("    Day::new::<day{day:02}::Day{day:02}>({day}),"),
+            )?,
+        ),
+    ];
+
+    let files = [
+        (dir.join("Cargo.toml"), manifest_template(day)),
+        (dir.join("src").join("lib.rs"), lib_template(day, title)),
+        (dir.join("example.txt"), String::new()),
+    ];
+    let write = |path: &Path, text: &str| {
+        fs::write(path, text).map_err(|e| format!("Couldn't write {}: {e}", path.display()))
+    };
+    fs::create_dir_all(dir.join("src"))
+        .map_err(|e| format!("Couldn't create {}: {e}", dir.display()))?;
+    let mut changed = Vec::new();
+    for (path, text) in files.iter().chain(&edits) {
+        write(path, text)?;
+        changed.push(path.clone());
+    }
+    Ok(changed)
+}
+
+/// Save each part's example from a puzzle page into the day's directory and
+/// its expected answer into the ledger. Part 2 shares part 1's file if its
+/// example is the same or it doesn't have one. Returns a line describing each
+/// part.
+pub fn save_examples(
+    root: &Path,
+    day: u8,
+    html: &str,
+    ledger: &mut Ledger,
+) -> Result<Vec<String>, String> {
+    let dir = root.join(format!("{day:02}"));
+    if !dir.is_dir() {
+        return Err(format!(
+            "{} doesn't exist. Make it with aoc new {day}.",
+            dir.display()
+        ));
+    }
+    let page = parse_page(html);
+    if page.iter().all(|part| part.examples.is_empty()) {
+        return Err("Couldn't find any examples on the page.".to_string());
+    }
+
+    let mut saved: Vec<(String, &str)> = Vec::new();
+    let mut lines = Vec::new();
+    for (part, page_part) in Part::BOTH.into_iter().zip(&page) {
+        let file = match page_part.example() {
+            Some(example) => match saved.iter().find(|(_, text)| *text == example) {
+                Some((file, _)) => file.clone(),
+                None => {
+                    let file = match saved.len() {
+                        0 => "example.txt".to_string(),
+                        n => format!("example{}.txt", n + 1),
+                    };
+                    let path = dir.join(&file);
+                    fs::write(&path, example)
+                        .map_err(|e| format!("Couldn't write {}: {e}", path.display()))?;
+                    saved.push((file.clone(), example));
+                    file
+                }
+            },
+            None => match saved.last() {
+                Some((file, _)) => file.clone(),
+                None => continue,
+            },
+        };
+        match &page_part.answer {
+            Some(answer) => {
+                ledger.set_example(YEAR, day, part, &file, answer);
+                lines.push(format!("Part {part}: {file} should give {answer}"));
+            }
+            None => lines.push(format!("Part {part}: {file}, but no answer found")),
+        }
+    }
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_sorted() {
+        let text = "[dependencies]\nutilities = 1\nday01 = 1\nday03 = 1\n";
+        assert_eq!(
+            insert_sorted(text, "day", "day02 = 1").unwrap(),
+            "[dependencies]\nutilities = 1\nday01 = 1\nday02 = 1\nday03 = 1\n"
+        );
+        assert_eq!(
+            insert_sorted(text, "day", "day10 = 1").unwrap(),
+            "[dependencies]\nutilities = 1\nday01 = 1\nday03 = 1\nday10 = 1\n"
+        );
+        assert!(insert_sorted(text, "nothing", "x").is_err());
+    }
+
+    #[test]
+    fn test_add_member() {
+        assert_eq!(
+            add_member(
+                "[workspace]\nmembers = [\"utilities\", \"aoc\", \"01\", \"05\"]\n",
+                3
+            )
+            .unwrap(),
+            "[workspace]\nmembers = [\"utilities\", \"aoc\", \"01\", \"03\", \"05\"]\n"
+        );
+        assert!(add_member("[workspace]\n", 3).is_err());
+    }
+
+    #[test]
+    fn test_save_examples_second_file() {
+        let root = std::env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("01")).unwrap();
+        let page = "<article><pre><code>1abc2\n</code></pre><code><em>12</em></code></article>\
+                    <article><pre><code>two1nine\n</code></pre><code><em>29</em></code></article>";
+        let mut ledger = Ledger::default();
+        save_examples(&root, 1, page, &mut ledger).unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("01/example2.txt")).unwrap(),
+            "two1nine\n"
+        );
+        assert_eq!(
+            ledger
+                .get(YEAR, 1, Part::Two)
+                .unwrap()
+                .example
+                .clone()
+                .unwrap()
+                .file,
+            "example2.txt"
+        );
+        assert!(save_examples(&root, 1, "<p>nothing</p>", &mut ledger).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::create_dir_all(root.join("05")).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"aoc\", \"05\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("aoc/Cargo.toml"),
+            "[dependencies]\nday05 = { path = \"../05\" }\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("aoc/src/days.rs"),
+            "pub const DAYS: &[Day] = &[\n    Day::new::<day05::Day05>(5),\n];\n",
+        )
+        .unwrap();
+
+        let changed = new_day(&root, 6, "Wait For It").unwrap();
+        assert_eq!(changed.len(), 6);
+        let lib = fs::read_to_string(root.join("06/src/lib.rs")).unwrap();
+        assert!(lib.starts_with("//! Day 06: Wait For It\n"));
+        assert!(lib.contains("const TITLE: &'static str = \"Wait For It\";"));
+        assert!(lib.contains("Day06::check_input(6, Part::Two);"));
+        assert!(lib.contains("Day06::check_example(6, Part::One);"));
+        // The runner solves every registered day, so stubs mustn't panic.
+        assert!(!lib.contains("todo!()"));
+        assert_eq!(
+            fs::read_to_string(root.join("Cargo.toml")).unwrap(),
+            "[workspace]\nmembers = [\"aoc\", \"05\", \"06\"]\n"
+        );
+        assert!(
+            fs::read_to_string(root.join("aoc/src/days.rs"))
+                .unwrap()
+                .contains("(5),\n    Day::new::<day06::Day06>(6),\n];")
+        );
+        assert!(
+            fs::read_to_string(root.join("aoc/Cargo.toml"))
+                .unwrap()
+                .ends_with("day06 = { path = \"../06\" }\n")
+        );
+
+        let page = "<article><pre><code>Time: 7\n</code></pre><code><em>288</em></code></article>\
+                    <article><p>Same example.</p><code><em>71503</em></code></article>";
+        let mut ledger = Ledger::default();
+        assert_eq!(
+            save_examples(&root, 6, page, &mut ledger).unwrap(),
+            vec![
+                "Part 1: example.txt should give 288",
+                "Part 2: example.txt should give 71503"
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("06/example.txt")).unwrap(),
+            "Time: 7\n"
+        );
+        let example = ledger.get(YEAR, 6, Part::Two).unwrap().example.clone();
+        assert_eq!(example.unwrap().answer, "71503");
+        assert!(save_examples(&root, 7, page, &mut ledger).is_err());
+
+        assert!(new_day(&root, 6, "Again").is_err());
+        assert!(new_day(&root, 26, "Too late").is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
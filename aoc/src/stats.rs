@@ -0,0 +1,84 @@
+//! Summary statistics for benchmark timings.
+
+use std::time::Duration;
+
+/// Summary of a set of timings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    /// Population standard deviation.
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Summarize the timings. Returns None if there aren't any.
+    pub fn new(times: &[Duration]) -> Option<Self> {
+        if times.is_empty() {
+            return None;
+        }
+        let mut sorted = times.to_vec();
+        sorted.sort();
+
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+
+        let count = times.len() as f64;
+        let mean = times.iter().map(Duration::as_secs_f64).sum::<f64>() / count;
+        let variance = times
+            .iter()
+            .map(|time| (time.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / count;
+
+        Some(Self {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|v| Duration::from_millis(*v)).collect()
+    }
+
+    fn assert_close(duration: Duration, millis: f64) {
+        assert!((duration.as_secs_f64() * 1000.0 - millis).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_stats_odd() {
+        let stats = Stats::new(&millis(&[5, 1, 3])).unwrap();
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_close(stats.mean, 3.0);
+    }
+
+    #[test]
+    fn test_stats_even() {
+        let stats = Stats::new(&millis(&[2, 4, 4, 4, 5, 5, 7, 9])).unwrap();
+        assert_eq!(stats.min, Duration::from_millis(2));
+        assert_eq!(stats.median, Duration::from_micros(4500));
+        assert_close(stats.mean, 5.0);
+        assert_close(stats.stddev, 2.0);
+    }
+
+    #[test]
+    fn test_stats_single_and_empty() {
+        let stats = Stats::new(&millis(&[7])).unwrap();
+        assert_eq!(stats.median, Duration::from_millis(7));
+        assert_eq!(stats.stddev, Duration::ZERO);
+        assert_eq!(Stats::new(&[]), None);
+    }
+}
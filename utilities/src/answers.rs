@@ -0,0 +1,551 @@
+//! Ledger of verified answers, and submitting new answers to the site.
+//!
+//! The ledger is a small TOML file with a table per day:
+//!
+//! ```toml
+//! next_submit = 1701410000
+//!
+//! [2023.day03]
+//! part1 = "539433"
+//! part2_wrong = ["75847566"]
+//! ```
+//!
+//! `partN` is the accepted answer and `partN_wrong` lists answers the site
+//! rejected. `partN_example` is an example file in the day's directory and its
+//! expected answer, like `["example.txt", "4361"]`. `next_submit` is the unix
+//! time the site's cooldown ends.
+
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    input::{FetchError, Inputs},
+    parse::{self, Cursor, InputError},
+    solution::Part,
+};
+
+/// How long the site makes you wait after a wrong answer if it doesn't say.
+const DEFAULT_COOLDOWN: u64 = 60;
+
+/// Whether an answer matches the ledger.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    /// Ledger doesn't know the right answer and hasn't seen this one rejected.
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// What the ledger knows about one part of a day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PartAnswers {
+    /// Answer the site accepted.
+    pub correct: Option<String>,
+    /// Answers the site rejected, in the order they were submitted.
+    pub wrong: Vec<String>,
+    /// Example from the puzzle description and its answer.
+    pub example: Option<Example>,
+}
+
+/// Example input file and the answer the puzzle says it has.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// File name in the day's directory.
+    pub file: String,
+    pub answer: String,
+}
+
+/// Error loading the ledger.
+#[derive(Debug)]
+pub enum LedgerError {
+    Io(io::Error),
+    Parse(InputError),
+}
+
+impl fmt::Display for LedgerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LedgerError::Io(e) => write!(f, "{e}"),
+            LedgerError::Parse(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for LedgerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LedgerError::Io(e) => Some(e),
+            LedgerError::Parse(e) => Some(e),
+        }
+    }
+}
+
+impl From<io::Error> for LedgerError {
+    fn from(e: io::Error) -> Self {
+        LedgerError::Io(e)
+    }
+}
+
+impl From<InputError> for LedgerError {
+    fn from(e: InputError) -> Self {
+        LedgerError::Parse(e)
+    }
+}
+
+/// Known answers keyed by year, day and part.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Ledger {
+    parts: BTreeMap<(u16, u8, Part), PartAnswers>,
+    /// Unix time in seconds before which nothing should be submitted.
+    pub next_submit: Option<u64>,
+}
+
+impl Ledger {
+    /// Get the ledger file from `AOC_ANSWERS`, or `answers.toml` in the repo root.
+    pub fn default_path() -> PathBuf {
+        match std::env::var("AOC_ANSWERS") {
+            Ok(path) if !path.trim().is_empty() => PathBuf::from(path),
+            _ => Path::new(env!("CARGO_MANIFEST_DIR"))
+                .parent()
+                .expect("utilities is inside the workspace")
+                .join("answers.toml"),
+        }
+    }
+
+    /// Read the ledger from a file. A missing file is an empty ledger.
+    pub fn load(path: &Path) -> Result<Self, LedgerError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Ok(Self::parse(&text)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write the ledger to a file.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    /// Parse the ledger from its TOML text.
+    pub fn parse(text: &str) -> Result<Self, InputError> {
+        let mut ledger = Self::default();
+        let mut day: Option<(u16, u8)> = None;
+        for mut line in parse::lines(text) {
+            line.skip_whitespace();
+            if line.is_empty() || line.rest().starts_with('#') {
+                continue;
+            }
+            if line.literal("[").is_ok() {
+                let year = line.unsigned()?;
+                line.literal(".day")?;
+                day = Some((year, line.unsigned()?));
+                line.literal("]")?;
+                line.end()?;
+                continue;
+            }
+
+            let key_start = line;
+            let key = line.word()?;
+            line.literal("=")?;
+            match (key, day) {
+                ("next_submit", None) => ledger.next_submit = Some(line.unsigned()?),
+                (_, Some((year, number))) => {
+                    let (part, field) = match key.split_once('_') {
+                        Some((part, field)) => (part, field),
+                        None => (key, ""),
+                    };
+                    let part = match part {
+                        "part1" => Part::One,
+                        "part2" => Part::Two,
+                        _ => return Err(key_start.error(format!("unknown key '{key}'"))),
+                    };
+                    let answers = ledger.parts.entry((year, number, part)).or_default();
+                    match field {
+                        "" => answers.correct = Some(line.quoted()?),
+                        "wrong" => answers.wrong = quoted_list(&mut line)?,
+                        "example" => {
+                            let list_start = line;
+                            let [file, answer]: [String; 2] =
+                                quoted_list(&mut line)?.try_into().map_err(|_| {
+                                    list_start.error("expected [\"<file>\", \"<answer>\"]")
+                                })?;
+                            answers.example = Some(Example { file, answer });
+                        }
+                        _ => return Err(key_start.error(format!("unknown key '{key}'"))),
+                    }
+                }
+                _ => return Err(key_start.error(format!("unknown key '{key}'"))),
+            }
+            line.end()?;
+        }
+        Ok(ledger)
+    }
+
+    /// Get what's known about a part.
+    pub fn get(&self, year: u16, day: u8, part: Part) -> Option<&PartAnswers> {
+        self.parts.get(&(year, day, part))
+    }
+
+    /// Check an answer against the ledger.
+    pub fn check(&self, year: u16, day: u8, part: Part, answer: &str) -> Verdict {
+        let Some(answers) = self.get(year, day, part) else {
+            return Verdict::Unknown;
+        };
+        match &answers.correct {
+            Some(correct) if correct == answer => Verdict::Correct,
+            Some(_) => Verdict::Incorrect,
+            None if answers.wrong.iter().any(|wrong| wrong == answer) => Verdict::Incorrect,
+            None => Verdict::Unknown,
+        }
+    }
+
+    /// Remember an example file and its answer.
+    pub fn set_example(&mut self, year: u16, day: u8, part: Part, file: &str, answer: &str) {
+        self.parts.entry((year, day, part)).or_default().example = Some(Example {
+            file: file.to_string(),
+            answer: answer.to_string(),
+        });
+    }
+
+    /// Remember whether the site accepted an answer.
+    pub fn record(&mut self, year: u16, day: u8, part: Part, answer: &str, correct: bool) {
+        let answers = self.parts.entry((year, day, part)).or_default();
+        if correct {
+            answers.correct = Some(answer.to_string());
+        } else if !answers.wrong.iter().any(|wrong| wrong == answer) {
+            answers.wrong.push(answer.to_string());
+        }
+    }
+}
+
+/// Parse a list of quoted strings in square brackets.
+fn quoted_list(line: &mut Cursor) -> Result<Vec<String>, InputError> {
+    line.literal("[")?;
+    if line.literal("]").is_ok() {
+        return Ok(Vec::new());
+    }
+    let items = line.separated_by(",", |item| item.quoted())?;
+    line.literal("]")?;
+    Ok(items)
+}
+
+/// Quote a string for TOML.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl fmt::Display for Ledger {
+    /// Format the ledger as TOML, the same way [Ledger::parse] reads it.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "# Verified answers. Checked by `aoc run` and updated by `aoc submit`."
+        )?;
+        if let Some(next_submit) = self.next_submit {
+            writeln!(f, "next_submit = {next_submit}")?;
+        }
+        let mut current: Option<(u16, u8)> = None;
+        for ((year, day, part), answers) in &self.parts {
+            if current != Some((*year, *day)) {
+                writeln!(f, "\n[{year}.day{day:02}]")?;
+                current = Some((*year, *day));
+            }
+            if let Some(correct) = &answers.correct {
+                writeln!(f, "part{part} = {}", quote(correct))?;
+            }
+            if let Some(example) = &answers.example {
+                writeln!(
+                    f,
+                    "part{part}_example = [{}, {}]",
+                    quote(&example.file),
+                    quote(&example.answer)
+                )?;
+            }
+            if !answers.wrong.is_empty() {
+                let wrong: Vec<String> = answers.wrong.iter().map(|w| quote(w)).collect();
+                writeln!(f, "part{part}_wrong = [{}]", wrong.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Result of submitting an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Submission {
+    /// Site accepted the answer.
+    Correct,
+    /// Site rejected the answer, with its hint like "too high" if it gave one.
+    Incorrect(Option<String>),
+    /// Still cooling down from the last submission, for this much longer.
+    Wait(Duration),
+    /// Ledger already knows whether the answer is right, so it wasn't sent.
+    Known(Verdict),
+    /// Site says this part is already solved.
+    AlreadySolved,
+}
+
+impl fmt::Display for Submission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Submission::Correct => write!(f, "That's the right answer!"),
+            Submission::Incorrect(Some(hint)) => {
+                write!(f, "That's not the right answer; it's {hint}.")
+            }
+            Submission::Incorrect(None) => write!(f, "That's not the right answer."),
+            Submission::Wait(wait) => write!(f, "Wait {}s before submitting.", wait.as_secs()),
+            Submission::Known(verdict) => {
+                write!(f, "Not submitted, the ledger says it's {verdict}.")
+            }
+            Submission::AlreadySolved => write!(f, "That part is already solved."),
+        }
+    }
+}
+
+/// Parse a wait like "1m 30s" or "45s" into seconds.
+fn parse_wait(text: &str) -> Option<u64> {
+    text.split_whitespace()
+        .map(|amount| {
+            let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+            let number: u64 = number.parse().ok()?;
+            match unit {
+                "h" => Some(number * 3600),
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+/// Work out what the site said about a submission from the page it sent back.
+/// Also returns how long to wait before the next submission, if it said.
+fn read_response(page: &str) -> Option<(Submission, Option<u64>)> {
+    if page.contains("That's the right answer") {
+        return Some((Submission::Correct, None));
+    }
+    if page.contains("Did you already complete it") {
+        return Some((Submission::AlreadySolved, None));
+    }
+    if let Some((_, rest)) = page.split_once("You have ")
+        && let Some((wait, _)) = rest.split_once(" left to wait")
+    {
+        let wait = parse_wait(wait)?;
+        return Some((Submission::Wait(Duration::from_secs(wait)), Some(wait)));
+    }
+    if page.contains("That's not the right answer") {
+        let hint = ["too high", "too low"]
+            .into_iter()
+            .find(|hint| page.contains(hint))
+            .map(String::from);
+        let wait = if let Some((_, rest)) = page.split_once("wait ")
+            && let Some((amount, _)) = rest.split_once(" minute")
+        {
+            match amount {
+                "one" => 60,
+                amount => amount.parse::<u64>().map_or(DEFAULT_COOLDOWN, |m| m * 60),
+            }
+        } else {
+            DEFAULT_COOLDOWN
+        };
+        return Some((Submission::Incorrect(hint), Some(wait)));
+    }
+    None
+}
+
+/// Submit an answer and record what the site says in the ledger. Nothing is
+/// sent if the ledger already knows the verdict or the cooldown from the last
+/// submission hasn't ended. `now` is the current unix time in seconds.
+pub fn submit(
+    inputs: &Inputs,
+    ledger: &mut Ledger,
+    (year, day, part): (u16, u8, Part),
+    answer: &str,
+    now: u64,
+) -> Result<Submission, FetchError> {
+    match ledger.check(year, day, part, answer) {
+        Verdict::Unknown => {}
+        verdict => return Ok(Submission::Known(verdict)),
+    }
+    if let Some(next_submit) = ledger.next_submit
+        && next_submit > now
+    {
+        return Ok(Submission::Wait(Duration::from_secs(next_submit - now)));
+    }
+
+    let session = inputs.session.as_ref().ok_or(FetchError::NoSession)?;
+    let url = format!("{}/{year}/day/{day}/answer", inputs.base_url);
+    let http_error = |message: String| FetchError::Http {
+        url: url.clone(),
+        message,
+    };
+    let level = part.to_string();
+    let mut response = ureq::post(&url)
+        .header("Cookie", &format!("session={session}"))
+        .header("User-Agent", "github.com/Ac5000/2023-advent-of-code")
+        .send_form([("level", level.as_str()), ("answer", answer)])
+        .map_err(|e| http_error(e.to_string()))?;
+    let page = response
+        .body_mut()
+        .read_to_string()
+        .map_err(|e| http_error(e.to_string()))?;
+
+    let (submission, wait) =
+        read_response(&page).ok_or_else(|| http_error("unrecognized response".to_string()))?;
+    ledger.next_submit = wait.map(|wait| now + wait);
+    match submission {
+        Submission::Correct => ledger.record(year, day, part, answer, true),
+        Submission::Incorrect(_) => ledger.record(year, day, part, answer, false),
+        _ => {}
+    }
+    Ok(submission)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::tests::{serve, temp_dir};
+
+    const LEDGER: &str = "# Verified answers.
+next_submit = 100
+
+[2023.day03]
+part1 = \"539433\"
+part2 = \"75847567\"
+
+[2023.day05]
+part1_wrong = [\"1\", \"a \\\"quoted\\\" answer\"]
+part1_example = [\"example.txt\", \"35\"]
+";
+
+    #[test]
+    fn test_parse_and_check() {
+        let ledger = Ledger::parse(LEDGER).unwrap();
+        assert_eq!(ledger.next_submit, Some(100));
+        assert_eq!(ledger.check(2023, 3, Part::One, "539433"), Verdict::Correct);
+        assert_eq!(ledger.check(2023, 3, Part::Two, "1"), Verdict::Incorrect);
+        assert_eq!(ledger.check(2023, 5, Part::One, "1"), Verdict::Incorrect);
+        assert_eq!(ledger.check(2023, 5, Part::One, "2"), Verdict::Unknown);
+        assert_eq!(ledger.check(2023, 4, Part::One, "1"), Verdict::Unknown);
+        assert_eq!(
+            ledger.get(2023, 5, Part::One).unwrap().wrong[1],
+            "a \"quoted\" answer"
+        );
+        assert_eq!(
+            ledger.get(2023, 5, Part::One).unwrap().example,
+            Some(Example {
+                file: "example.txt".to_string(),
+                answer: "35".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut ledger = Ledger::parse(LEDGER).unwrap();
+        ledger.record(2023, 1, Part::Two, "281", true);
+        ledger.record(2023, 5, Part::One, "1", false);
+        ledger.record(2023, 5, Part::One, "3", false);
+        ledger.set_example(2023, 1, Part::Two, "example2.txt", "281");
+        let text = ledger.to_string();
+        assert!(text.contains(
+            "\n[2023.day01]\npart2 = \"281\"\npart2_example = [\"example2.txt\", \"281\"]\n"
+        ));
+        assert_eq!(Ledger::parse(&text).unwrap(), ledger);
+        assert_eq!(ledger.get(2023, 5, Part::One).unwrap().wrong.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = Ledger::parse("[2023.day01]\npart3 = \"1\"").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert!(Ledger::parse("part1 = \"1\"").is_err());
+        assert!(Ledger::parse("[2023.day01]\npart1 = 1").is_err());
+        assert!(Ledger::parse("[2023.day01]\npart1_wrong = [\"1\"").is_err());
+        assert!(Ledger::parse("[2023.day01]\npart1_example = [\"example.txt\"]").is_err());
+        assert!(Ledger::parse("[2023.day01]\npart1_other = \"1\"").is_err());
+    }
+
+    #[test]
+    fn test_read_response() {
+        assert_eq!(
+            read_response("<p>That's the right answer!  You are one gold star closer.</p>"),
+            Some((Submission::Correct, None))
+        );
+        assert_eq!(
+            read_response(
+                "That's not the right answer; your answer is too high.  Please wait one minute before trying again."
+            ),
+            Some((
+                Submission::Incorrect(Some("too high".to_string())),
+                Some(60)
+            ))
+        );
+        assert_eq!(
+            read_response(
+                "That's not the right answer.  Please wait 5 minutes before trying again."
+            ),
+            Some((Submission::Incorrect(None), Some(300)))
+        );
+        assert_eq!(
+            read_response("You gave an answer too recently.  You have 1m 5s left to wait."),
+            Some((Submission::Wait(Duration::from_secs(65)), Some(65)))
+        );
+        assert_eq!(
+            read_response(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            Some((Submission::AlreadySolved, None))
+        );
+        assert_eq!(read_response("<html>Something else</html>"), None);
+    }
+
+    #[test]
+    fn test_submit() {
+        let (url, server) = serve(vec![
+            (200, "That's not the right answer; your answer is too low."),
+            (200, "That's the right answer!"),
+        ]);
+        let mut inputs = Inputs::new(temp_dir("submit"), Some("abc".to_string()));
+        inputs.base_url = url;
+        let mut ledger = Ledger::default();
+        let key = (2023, 5, Part::Two);
+
+        let result = submit(&inputs, &mut ledger, key, "10", 1000).unwrap();
+        assert_eq!(result, Submission::Incorrect(Some("too low".to_string())));
+        assert_eq!(ledger.next_submit, Some(1060));
+        assert_eq!(ledger.check(2023, 5, Part::Two, "10"), Verdict::Incorrect);
+
+        let result = submit(&inputs, &mut ledger, key, "10", 2000).unwrap();
+        assert_eq!(result, Submission::Known(Verdict::Incorrect));
+        let result = submit(&inputs, &mut ledger, key, "46", 1030).unwrap();
+        assert_eq!(result, Submission::Wait(Duration::from_secs(30)));
+
+        let result = submit(&inputs, &mut ledger, key, "46", 1060).unwrap();
+        assert_eq!(result, Submission::Correct);
+        assert_eq!(ledger.check(2023, 5, Part::Two, "46"), Verdict::Correct);
+        assert_eq!(ledger.next_submit, None);
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].starts_with("POST /2023/day/5/answer HTTP/1.1"));
+        assert!(requests[0].ends_with("level=2&answer=10"));
+        assert!(requests[1].ends_with("level=2&answer=46"));
+        fs::remove_dir_all(&inputs.cache_dir).unwrap();
+    }
+}
@@ -1,6 +1,7 @@
 //! Used to add colors to text when it prints to the terminal.
 
 const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_DIM: &str = "\x1b[2m";
 const ANSI_BLACK: &str = "\x1b[30m";
 const ANSI_RED: &str = "\x1b[31m";
 const ANSI_GREEN: &str = "\x1b[32m";
@@ -50,6 +51,11 @@ pub fn white(text: &str) -> String {
     "".to_owned() + ANSI_WHITE + text + ANSI_RESET
 }
 
+/// Make the text dim.
+pub fn dim(text: &str) -> String {
+    "".to_owned() + ANSI_DIM + text + ANSI_RESET
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
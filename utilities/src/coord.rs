@@ -5,24 +5,61 @@ use std::{
     collections::HashSet,
     fmt,
     hash::Hash,
-    ops::{Add, Sub},
+    ops::{Add, AddAssign, Div, Mul, Neg, Rem, Sub},
 };
 
-/// Structure representing a coordinate on the grid.
+use crate::direction::Direction;
+
+/// Signed integer types a coordinate can be made of.
+pub trait Signed:
+    Copy
+    + Eq
+    + Ord
+    + Hash
+    + fmt::Debug
+    + fmt::Display
+    + From<i8>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+{
+}
+
+impl Signed for i8 {}
+impl Signed for i16 {}
+impl Signed for i32 {}
+impl Signed for i64 {}
+impl Signed for i128 {}
+impl Signed for isize {}
+
+/// Absolute value that works for any [Signed] type.
+pub(crate) fn abs<T: Signed>(value: T) -> T {
+    if value < T::from(0) { -value } else { value }
+}
+
+/// Structure representing a coordinate on the grid. Plain `Coord` is `i32`,
+/// which is what grids use. Use a wider type like `Coord<i64>` for puzzles
+/// where the numbers get big.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-pub struct Coord {
+pub struct Coord<T = i32> {
     /// X coordinate.
-    pub x: i32,
+    pub x: T,
     /// Y coordinate.
-    pub y: i32,
+    pub y: T,
 }
 
-impl Coord {
+impl<T> Coord<T> {
     /// Make a new Coord from x and y coordinates.
-    pub const fn new(x: i32, y: i32) -> Self {
-        Self { x: x, y: y }
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
     }
+}
 
+impl Coord {
     /// Return a cordinate north/up from this coordinate.
     pub const fn north(&self) -> Self {
         Self::new(self.x, self.y - 1)
@@ -63,29 +100,202 @@ impl Coord {
         Self::new(self.x - 1, self.y - 1)
     }
 
+    /// Return the coordinate one step in the direction from this coordinate.
+    pub const fn step(&self, dir: Direction) -> Self {
+        self.step_n(dir, 1)
+    }
+
+    /// Return the coordinate `n` steps in the direction from this coordinate.
+    pub const fn step_n(&self, dir: Direction, n: i32) -> Self {
+        let offset = dir.offset();
+        Self::new(self.x + offset.x * n, self.y + offset.y * n)
+    }
+
+    /// Iterate the north, east, south and west neighbours without allocating.
+    pub fn neighbors4(&self) -> impl Iterator<Item = Coord> + use<> {
+        let coord = *self;
+        Direction::CARDINAL
+            .into_iter()
+            .map(move |dir| coord.step(dir))
+    }
+
+    /// Iterate all eight surrounding neighbours without allocating. Starts north
+    /// and goes clockwise.
+    pub fn neighbors8(&self) -> impl Iterator<Item = Coord> + use<> {
+        let coord = *self;
+        Direction::ALL.into_iter().map(move |dir| coord.step(dir))
+    }
+
     /// Get surrounding coordinates.
     pub fn get_surrounding_coords(&self) -> HashSet<Coord> {
-        HashSet::from([
-            self.north(),
-            self.northeast(),
-            self.east(),
-            self.southeast(),
-            self.south(),
-            self.southwest(),
-            self.west(),
-            self.northwest(),
-        ])
+        self.neighbors8().collect()
+    }
+}
+
+/// Moving around the grid with coordinates of any type. Plain `Coord` has the
+/// same methods built in as `const fn`s, which can't use trait arithmetic, so
+/// this is only needed for wider types like `Coord<i64>` and generic code.
+pub trait Steps: Sized {
+    /// Type of the parts of the coordinate.
+    type Unit: From<i8>;
+
+    /// Return the coordinate `n` steps in the direction from this coordinate.
+    fn step_n(&self, dir: Direction, n: Self::Unit) -> Self;
+
+    /// Return the coordinate one step in the direction from this coordinate.
+    fn step(&self, dir: Direction) -> Self {
+        self.step_n(dir, Self::Unit::from(1))
+    }
+
+    /// Return a cordinate north/up from this coordinate.
+    fn north(&self) -> Self {
+        self.step(Direction::N)
+    }
+
+    /// Return a cordinate northeast/up-right from this coordinate.
+    fn northeast(&self) -> Self {
+        self.step(Direction::NE)
+    }
+
+    /// Return a cordinate east/right from this coordinate.
+    fn east(&self) -> Self {
+        self.step(Direction::E)
+    }
+
+    /// Return a cordinate southeast/down-right from this coordinate.
+    fn southeast(&self) -> Self {
+        self.step(Direction::SE)
+    }
+
+    /// Return a cordinate south/down from this coordinate.
+    fn south(&self) -> Self {
+        self.step(Direction::S)
+    }
+
+    /// Return a cordinate southwest/down-left from this coordinate.
+    fn southwest(&self) -> Self {
+        self.step(Direction::SW)
+    }
+
+    /// Return a cordinate west/left from this coordinate.
+    fn west(&self) -> Self {
+        self.step(Direction::W)
+    }
+
+    /// Return a cordinate northwest/up-left from this coordinate.
+    fn northwest(&self) -> Self {
+        self.step(Direction::NW)
+    }
+
+    /// Iterate the north, east, south and west neighbours.
+    fn neighbors4(&self) -> impl Iterator<Item = Self> {
+        Direction::CARDINAL.into_iter().map(|dir| self.step(dir))
+    }
+
+    /// Iterate all eight surrounding neighbours. Starts north and goes
+    /// clockwise.
+    fn neighbors8(&self) -> impl Iterator<Item = Self> {
+        Direction::ALL.into_iter().map(|dir| self.step(dir))
+    }
+}
+
+impl<T: Signed> Steps for Coord<T> {
+    type Unit = T;
+
+    fn step_n(&self, dir: Direction, n: T) -> Self {
+        // Offsets are all -1, 0 or 1 so they fit in any type.
+        let offset = dir.offset();
+        let offset = Self::new(T::from(offset.x as i8), T::from(offset.y as i8));
+        *self + offset * n
+    }
+}
+
+impl<T: Signed> Coord<T> {
+    /// Get the manhattan/taxicab distance to the other coordinate.
+    pub fn manhattan(&self, other: &Self) -> T {
+        let diff = *self - *other;
+        abs(diff.x) + abs(diff.y)
+    }
+
+    /// Get the chebyshev distance to the other coordinate, the number of king
+    /// moves to get there.
+    pub fn chebyshev(&self, other: &Self) -> T {
+        let diff = *self - *other;
+        abs(diff.x).max(abs(diff.y))
+    }
+
+    /// Get the square of the straight line distance to the other coordinate.
+    pub fn euclidean_sq(&self, other: &Self) -> T {
+        let diff = *self - *other;
+        diff.x * diff.x + diff.y * diff.y
+    }
+
+    /// Get -1, 0 or 1 for each part depending on its sign. Turns an offset into
+    /// a single step in the same general direction.
+    pub fn signum(&self) -> Self {
+        let signum = |value: T| T::from((value > T::from(0)) as i8 - (value < T::from(0)) as i8);
+        Self::new(signum(self.x), signum(self.y))
+    }
+
+    /// Rotate 90° clockwise (as drawn, with north up) around `origin`.
+    pub fn rotate_right(&self, origin: &Self) -> Self {
+        let diff = *self - *origin;
+        *origin + Self::new(-diff.y, diff.x)
+    }
+
+    /// Rotate 90° counter-clockwise (as drawn, with north up) around `origin`.
+    pub fn rotate_left(&self, origin: &Self) -> Self {
+        let diff = *self - *origin;
+        *origin + Self::new(diff.y, -diff.x)
+    }
+
+    /// Mirror left to right over the vertical line at `x`.
+    pub fn reflect_x(&self, x: T) -> Self {
+        Self::new(x + x - self.x, self.y)
+    }
+
+    /// Mirror top to bottom over the horizontal line at `y`.
+    pub fn reflect_y(&self, y: T) -> Self {
+        Self::new(self.x, y + y - self.y)
+    }
+
+    /// Iterate the coordinates on the straight line from here to `end`,
+    /// including both ends, using Bresenham's algorithm.
+    pub fn line_to(&self, end: &Self) -> Line<T> {
+        let diff = *end - *self;
+        let delta = Self::new(abs(diff.x), -abs(diff.y));
+        Line {
+            next: Some(*self),
+            end: *end,
+            step: diff.signum(),
+            delta,
+            error: delta.x + delta.y,
+        }
+    }
+
+    /// Convert to a coordinate of a wider type. Always works.
+    pub fn cast<U: Signed + From<T>>(self) -> Coord<U> {
+        Coord::new(U::from(self.x), U::from(self.y))
+    }
+
+    /// Convert to a coordinate of another type, or None if either part doesn't
+    /// fit in it.
+    pub fn checked_cast<U: Signed + TryFrom<T>>(self) -> Option<Coord<U>> {
+        Some(Coord::new(
+            U::try_from(self.x).ok()?,
+            U::try_from(self.y).ok()?,
+        ))
     }
 }
 
-impl fmt::Display for Coord {
+impl<T: fmt::Display> fmt::Display for Coord<T> {
     /// Format the coordinate to print out nicely.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "({}, {})", self.x, self.y)
     }
 }
 
-impl Add for Coord {
+impl<T: Signed> Add for Coord<T> {
     type Output = Self;
 
     /// Add two coordinates together to make a third. Useful for offsetting a distance.
@@ -94,7 +304,15 @@ impl Add for Coord {
     }
 }
 
-impl Sub for Coord {
+impl<T: Signed> AddAssign for Coord<T> {
+    /// Move this coordinate by an offset.
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: Signed> Sub for Coord<T> {
     type Output = Self;
 
     /// Subtract two coordinates to see the x,y distance between them.
@@ -103,6 +321,171 @@ impl Sub for Coord {
     }
 }
 
+impl<T: Signed> Mul<T> for Coord<T> {
+    type Output = Self;
+
+    /// Scale both parts of the coordinate.
+    fn mul(self, scale: T) -> Self {
+        Self::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl<T: Signed> Neg for Coord<T> {
+    type Output = Self;
+
+    /// Point the other way.
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+/// Iterator over the coordinates on a line. Made by [Coord::line_to].
+#[derive(Clone, Debug)]
+pub struct Line<T> {
+    next: Option<Coord<T>>,
+    end: Coord<T>,
+    /// Direction to step in on each axis.
+    step: Coord<T>,
+    /// Distance to cover on each axis, with y negative.
+    delta: Coord<T>,
+    /// How far off the ideal line the next point is, scaled to stay whole.
+    error: T,
+}
+
+impl<T: Signed> Iterator for Line<T> {
+    type Item = Coord<T>;
+
+    fn next(&mut self) -> Option<Coord<T>> {
+        let current = self.next?;
+        if current == self.end {
+            self.next = None;
+            return Some(current);
+        }
+        let mut next = current;
+        let doubled = self.error + self.error;
+        if doubled >= self.delta.y {
+            self.error += self.delta.y;
+            next.x += self.step.x;
+        }
+        if doubled <= self.delta.x {
+            self.error += self.delta.x;
+            next.y += self.step.y;
+        }
+        self.next = Some(next);
+        Some(current)
+    }
+}
+
+/// Structure representing a coordinate in 3D space. Works like [Coord] with z
+/// added.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Coord3<T = i32> {
+    /// X coordinate.
+    pub x: T,
+    /// Y coordinate.
+    pub y: T,
+    /// Z coordinate.
+    pub z: T,
+}
+
+impl<T> Coord3<T> {
+    /// Make a new Coord3 from x, y and z coordinates.
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Signed> Coord3<T> {
+    /// Iterate the six neighbours that share a face with this coordinate.
+    pub fn neighbors6(&self) -> impl Iterator<Item = Self> + use<T> {
+        let coord = *self;
+        let (zero, one) = (T::from(0), T::from(1));
+        [
+            Self::new(-one, zero, zero),
+            Self::new(one, zero, zero),
+            Self::new(zero, -one, zero),
+            Self::new(zero, one, zero),
+            Self::new(zero, zero, -one),
+            Self::new(zero, zero, one),
+        ]
+        .into_iter()
+        .map(move |offset| coord + offset)
+    }
+
+    /// Iterate all 26 surrounding neighbours, the ones sharing a face, an edge
+    /// or a corner.
+    pub fn neighbors26(&self) -> impl Iterator<Item = Self> + use<T> {
+        let coord = *self;
+        (-1..=1_i8)
+            .flat_map(|z| (-1..=1_i8).flat_map(move |y| (-1..=1_i8).map(move |x| (x, y, z))))
+            .filter(|offset| *offset != (0, 0, 0))
+            .map(move |(x, y, z)| coord + Self::new(T::from(x), T::from(y), T::from(z)))
+    }
+
+    /// Get the manhattan/taxicab distance to the other coordinate.
+    pub fn manhattan(&self, other: &Self) -> T {
+        let diff = *self - *other;
+        abs(diff.x) + abs(diff.y) + abs(diff.z)
+    }
+
+    /// Get the 2D coordinate with only x and y.
+    pub fn xy(&self) -> Coord<T> {
+        Coord::new(self.x, self.y)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Coord3<T> {
+    /// Format the coordinate to print out nicely.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl<T: Signed> Add for Coord3<T> {
+    type Output = Self;
+
+    /// Add two coordinates together to make a third.
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Signed> AddAssign for Coord3<T> {
+    /// Move this coordinate by an offset.
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+        self.z += other.z;
+    }
+}
+
+impl<T: Signed> Sub for Coord3<T> {
+    type Output = Self;
+
+    /// Subtract two coordinates to see the x,y,z distance between them.
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T: Signed> Mul<T> for Coord3<T> {
+    type Output = Self;
+
+    /// Scale all parts of the coordinate.
+    fn mul(self, scale: T) -> Self {
+        Self::new(self.x * scale, self.y * scale, self.z * scale)
+    }
+}
+
+impl<T: Signed> Neg for Coord3<T> {
+    type Output = Self;
+
+    /// Point the other way.
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,6 +514,28 @@ mod tests {
         assert_eq!(cord.get_surrounding_coords(), expected);
     }
 
+    #[test]
+    fn test_step() {
+        let coord = Coord::new(1, 1);
+        assert_eq!(coord.step(Direction::N), coord.north());
+        assert_eq!(coord.step(Direction::SW), coord.southwest());
+        assert_eq!(coord.step_n(Direction::E, 3), Coord::new(4, 1));
+        assert_eq!(coord.step_n(Direction::NW, 2), Coord::new(-1, -1));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let coord = Coord::new(1, 1);
+        assert_eq!(
+            coord.neighbors4().collect::<Vec<Coord>>(),
+            vec![coord.north(), coord.east(), coord.south(), coord.west()]
+        );
+        assert_eq!(
+            coord.neighbors8().collect::<HashSet<Coord>>(),
+            coord.get_surrounding_coords()
+        );
+    }
+
     #[test]
     fn test_add_coords() {
         let coord = Coord::new(1, 1);
@@ -144,4 +549,142 @@ mod tests {
         let coord2 = Coord::new(1, 1);
         assert_eq!(coord - coord2, Coord::new(0, 0));
     }
+
+    #[test]
+    fn test_const_steps() {
+        const START: Coord = Coord::new(2, 2).north().step_n(Direction::E, 3);
+        assert_eq!(START, Coord::new(5, 1));
+        // Wider types move with the Steps trait, which agrees with the
+        // built in methods.
+        let wide: Coord<i64> = Coord::new(2, 2);
+        assert_eq!(wide.north().step_n(Direction::E, 3), Coord::new(5, 1));
+        assert_eq!(
+            wide.neighbors8().collect::<Vec<Coord<i64>>>(),
+            Coord::new(2, 2)
+                .neighbors8()
+                .map(|coord| coord.cast())
+                .collect::<Vec<Coord<i64>>>()
+        );
+    }
+
+    #[test]
+    fn test_generic_ops() {
+        let mut coord = Coord::new(1, -2);
+        coord += Coord::new(2, 2);
+        assert_eq!(coord, Coord::new(3, 0));
+        assert_eq!(coord * 3, Coord::new(9, 0));
+        assert_eq!(-coord, Coord::new(-3, 0));
+
+        let far: Coord<i64> = Coord::new(3_000_000_000, -1);
+        assert_eq!(far + far, Coord::new(6_000_000_000, -2));
+        assert_eq!(
+            far.step_n(Direction::SW, 1_000_000_000),
+            Coord::new(2_000_000_000, 999_999_999)
+        );
+        assert_eq!(far.north(), Coord::new(3_000_000_000, -2));
+        assert_eq!(far.to_string(), "(3000000000, -1)");
+    }
+
+    #[test]
+    fn test_casts() {
+        let coord = Coord::new(5, -7);
+        let wide: Coord<i64> = coord.cast();
+        assert_eq!(wide, Coord::new(5_i64, -7));
+        assert_eq!(wide.checked_cast::<i32>(), Some(coord));
+        assert_eq!(coord.checked_cast::<i8>(), Some(Coord::new(5_i8, -7)));
+        assert_eq!(Coord::new(1_i64 << 40, 0).checked_cast::<i32>(), None);
+        assert_eq!(Coord::new(0, 300).checked_cast::<i8>(), None);
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Coord::new(1, 6);
+        let b = Coord::new(5, 11);
+        assert_eq!(a.manhattan(&b), 9);
+        assert_eq!(b.manhattan(&a), 9);
+        assert_eq!(a.chebyshev(&b), 5);
+        assert_eq!(a.euclidean_sq(&b), 41);
+        assert_eq!((b - a).signum(), Coord::new(1, 1));
+        assert_eq!(Coord::new(0, -7).signum(), Coord::new(0, -1));
+        let far: Coord<i64> = Coord::new(-3_000_000_000, 0);
+        assert_eq!(far.manhattan(&Coord::new(0, 0)), 3_000_000_000);
+    }
+
+    #[test]
+    fn test_rotate_and_reflect() {
+        let origin = Coord::new(1, 1);
+        let north = origin.north();
+        assert_eq!(north.rotate_right(&origin), origin.east());
+        assert_eq!(north.rotate_left(&origin), origin.west());
+        let mut coord = Coord::new(4, 3);
+        for _ in 0..4 {
+            coord = coord.rotate_right(&origin);
+        }
+        assert_eq!(coord, Coord::new(4, 3));
+        assert_eq!(coord.rotate_left(&origin).rotate_right(&origin), coord);
+
+        assert_eq!(coord.reflect_x(0), Coord::new(-4, 3));
+        assert_eq!(coord.reflect_x(5), Coord::new(6, 3));
+        assert_eq!(coord.reflect_y(1), Coord::new(4, -1));
+    }
+
+    #[test]
+    fn test_line_to() {
+        let line: Vec<Coord> = Coord::new(0, 0).line_to(&Coord::new(6, 4)).collect();
+        assert_eq!(
+            line,
+            vec![
+                Coord::new(0, 0),
+                Coord::new(1, 1),
+                Coord::new(2, 1),
+                Coord::new(3, 2),
+                Coord::new(4, 3),
+                Coord::new(5, 3),
+                Coord::new(6, 4),
+            ]
+        );
+        let back: Vec<Coord> = Coord::new(6, 4).line_to(&Coord::new(0, 0)).collect();
+        assert_eq!(back.len(), 7);
+        assert_eq!(back.first(), Some(&Coord::new(6, 4)));
+        assert_eq!(
+            Coord::new(2, 5)
+                .line_to(&Coord::new(2, 1))
+                .collect::<Vec<Coord>>(),
+            vec![
+                Coord::new(2, 5),
+                Coord::new(2, 4),
+                Coord::new(2, 3),
+                Coord::new(2, 2),
+                Coord::new(2, 1)
+            ]
+        );
+        assert_eq!(Coord::new(3, 3).line_to(&Coord::new(3, 3)).count(), 1);
+        assert_eq!(
+            Coord::new(0, 0).line_to(&Coord::new(-3, 3)).last(),
+            Some(Coord::new(-3, 3))
+        );
+    }
+
+    #[test]
+    fn test_coord3() {
+        let coord = Coord3::new(1, 2, 3);
+        assert_eq!(coord + Coord3::new(1, 1, 1), Coord3::new(2, 3, 4));
+        assert_eq!(coord - Coord3::new(1, 1, 1), Coord3::new(0, 1, 2));
+        assert_eq!(-coord * 2, Coord3::new(-2, -4, -6));
+        assert_eq!(coord.manhattan(&Coord3::new(-1, 2, 6)), 5);
+        assert_eq!(coord.xy(), Coord::new(1, 2));
+        assert_eq!(coord.to_string(), "(1, 2, 3)");
+
+        let six: HashSet<Coord3> = coord.neighbors6().collect();
+        assert_eq!(six.len(), 6);
+        assert!(six.iter().all(|n| n.manhattan(&coord) == 1));
+        let all: HashSet<Coord3> = coord.neighbors26().collect();
+        assert_eq!(all.len(), 26);
+        assert!(all.is_superset(&six));
+        assert!(!all.contains(&coord));
+
+        let mut far: Coord3<i64> = Coord3::new(0, 0, 1 << 40);
+        far += Coord3::new(1, 1, 1);
+        assert_eq!(far.z, (1 << 40) + 1);
+    }
 }
@@ -0,0 +1,122 @@
+//! Module for compass directions on a grid. North is up (y - 1) to match how the
+//! puzzle inputs are read in.
+
+use std::fmt;
+
+use crate::coord::Coord;
+
+/// One of the eight compass directions.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction {
+    /// The four straight directions, clockwise from north.
+    pub const CARDINAL: [Direction; 4] = [Direction::N, Direction::E, Direction::S, Direction::W];
+
+    /// All eight directions, clockwise from north.
+    pub const ALL: [Direction; 8] = [
+        Direction::N,
+        Direction::NE,
+        Direction::E,
+        Direction::SE,
+        Direction::S,
+        Direction::SW,
+        Direction::W,
+        Direction::NW,
+    ];
+
+    /// Position of the direction in [Direction::ALL].
+    const fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Direction that is `eighths` 45° steps clockwise from this one.
+    const fn rotate(self, eighths: usize) -> Self {
+        Self::ALL[(self.index() + eighths) % 8]
+    }
+
+    /// Turn 90° to the left (counter-clockwise).
+    pub const fn turn_left(self) -> Self {
+        self.rotate(6)
+    }
+
+    /// Turn 90° to the right (clockwise).
+    pub const fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// Turn around to face the opposite way.
+    pub const fn reverse(self) -> Self {
+        self.rotate(4)
+    }
+
+    /// Is this one of the four straight directions.
+    pub const fn is_cardinal(self) -> bool {
+        matches!(
+            self,
+            Direction::N | Direction::E | Direction::S | Direction::W
+        )
+    }
+
+    /// Offset to move one step in this direction.
+    pub const fn offset(self) -> Coord {
+        match self {
+            Direction::N => Coord::new(0, -1),
+            Direction::NE => Coord::new(1, -1),
+            Direction::E => Coord::new(1, 0),
+            Direction::SE => Coord::new(1, 1),
+            Direction::S => Coord::new(0, 1),
+            Direction::SW => Coord::new(-1, 1),
+            Direction::W => Coord::new(-1, 0),
+            Direction::NW => Coord::new(-1, -1),
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    /// Format the direction as its compass abbreviation.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::N.turn_left(), Direction::W);
+        assert_eq!(Direction::N.turn_right(), Direction::E);
+        assert_eq!(Direction::W.turn_right(), Direction::N);
+        assert_eq!(Direction::NE.turn_left(), Direction::NW);
+        assert_eq!(Direction::SE.turn_right(), Direction::SW);
+        assert_eq!(Direction::S.reverse(), Direction::N);
+        assert_eq!(Direction::SW.reverse(), Direction::NE);
+    }
+
+    #[test]
+    fn test_turns_round_trip() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.reverse().reverse(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.reverse());
+            assert_eq!(dir.offset() + dir.reverse().offset(), Coord::new(0, 0));
+        }
+    }
+
+    #[test]
+    fn test_is_cardinal() {
+        assert!(Direction::CARDINAL.iter().all(|d| d.is_cardinal()));
+        assert!(!Direction::NE.is_cardinal());
+    }
+}
@@ -0,0 +1,151 @@
+//! Pulling examples and their answers out of a saved puzzle page.
+//!
+//! Each part of the puzzle is an `<article>`. Its example input is the first
+//! `<pre><code>` block, and its expected answer is the last emphasized code,
+//! like `<code><em>142</em></code>`, outside of the examples.
+
+/// Example and answer for one part of a puzzle page.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PagePart {
+    /// Every `<pre><code>` block in the part, as plain text.
+    pub examples: Vec<String>,
+    /// Last emphasized code outside the examples, usually the example's answer.
+    pub answer: Option<String>,
+}
+
+impl PagePart {
+    /// Get the main example of the part.
+    pub fn example(&self) -> Option<&str> {
+        self.examples.first().map(String::as_str)
+    }
+}
+
+/// Get every part of the puzzle on the page, in order.
+pub fn parse_page(html: &str) -> Vec<PagePart> {
+    let articles = blocks(html, "<article", "</article>");
+    let articles = if articles.is_empty() {
+        vec![html]
+    } else {
+        articles
+    };
+    articles
+        .into_iter()
+        .map(|article| PagePart {
+            examples: blocks(article, "<pre><code>", "</code></pre>")
+                .into_iter()
+                .map(text)
+                .collect(),
+            answer: answer(article),
+        })
+        .collect()
+}
+
+/// Get the text between every `open` and the next `close`, leaving out both.
+fn blocks<'a>(html: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    let mut blocks = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find(open) {
+        let after = &rest[start + open.len()..];
+        let Some(end) = after.find(close) else {
+            break;
+        };
+        blocks.push(&after[..end]);
+        rest = &after[end + close.len()..];
+    }
+    blocks
+}
+
+/// Get the last emphasized code in the article that isn't in an example.
+fn answer(article: &str) -> Option<String> {
+    let mut outside = String::new();
+    let mut rest = article;
+    while let Some(start) = rest.find("<pre>") {
+        outside.push_str(&rest[..start]);
+        rest = rest[start..]
+            .split_once("</pre>")
+            .map_or("", |(_, after)| after);
+    }
+    outside.push_str(rest);
+
+    [
+        ("<code><em>", "</em></code>"),
+        ("<em><code>", "</code></em>"),
+    ]
+    .into_iter()
+    .filter_map(|(open, close)| {
+        let start = outside.rfind(open)?;
+        let (answer, _) = outside[start + open.len()..].split_once(close)?;
+        Some((start, text(answer)))
+    })
+    .max_by_key(|(start, _)| *start)
+    .map(|(_, answer)| answer)
+}
+
+/// Turn HTML into plain text by dropping tags and decoding entities.
+fn text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+</code></pre>
+<p>In this example, the calibration values are <code>12</code>, <code>38</code>, <code>15</code>, and <code>77</code>. Adding these together produces <code><em>142</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>54388</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>two1nine
+<em>eight</em>wothree
+x &lt; y &amp;&amp; z
+</code></pre>
+<p>Adding these together produces <em><code>281</code></em>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_parse_page() {
+        let parts = parse_page(PAGE);
+        assert_eq!(parts.len(), 2);
+        assert_eq!(
+            parts[0].example(),
+            Some("1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n")
+        );
+        assert_eq!(parts[0].answer.as_deref(), Some("142"));
+        assert_eq!(
+            parts[1].example(),
+            Some("two1nine\neightwothree\nx < y && z\n")
+        );
+        assert_eq!(parts[1].answer.as_deref(), Some("281"));
+    }
+
+    #[test]
+    fn test_answer_skips_examples() {
+        let article = "<pre><code><em>7</em></code></pre><p>gives <code><em>3</em></code>.</p>\
+                       <pre><code><code><em>9</em></code></code></pre>";
+        assert_eq!(answer(article).as_deref(), Some("3"));
+        assert_eq!(answer("<p>No answer.</p>"), None);
+        assert_eq!(parse_page("<p>no articles</p>")[0].examples.len(), 0);
+    }
+}
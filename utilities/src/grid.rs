@@ -1,74 +1,380 @@
 //! Module for making a grid or map. Having done AoC once before, I know that having
 //! a reusable base for making grids is useful.
-use std::{collections::HashMap, fmt};
+//!
+//! Cells are stored densely in row-major order so lookups are just an index
+//! calculation and iterating the grid always goes left to right, top to bottom.
+//! Grids start at (0, 0) but can be moved anywhere, negative coordinates
+//! included, with [Grid::with_origin].
+use std::{
+    collections::HashSet,
+    error::Error,
+    fmt, io,
+    ops::{Index, IndexMut},
+};
 
 use crate::color_text::cyan;
 use crate::coord::Coord;
+use crate::rect::Rect;
 
-/// Structure representing a grid/map/2D array.
+/// Errors that can happen while loading a grid.
 #[derive(Debug)]
-pub struct Grid {
-    pub char_map: HashMap<Coord, char>,
-    pub max_x: i32,
-    pub max_y: i32,
+pub enum GridError {
+    /// Couldn't read the input.
+    Io(io::Error),
+    /// A line wasn't the same length as the first line. `line` starts at 1.
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// Input had no cells at all.
+    Empty,
+    /// The cell parser rejected a character.
+    InvalidChar { coord: Coord, ch: char },
 }
 
-impl Grid {
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::Io(e) => write!(f, "couldn't read grid: {e}"),
+            GridError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(f, "line {line} has {found} chars, expected {expected}"),
+            GridError::Empty => write!(f, "grid is empty"),
+            GridError::InvalidChar { coord, ch } => write!(f, "invalid char {ch:?} at {coord}"),
+        }
+    }
+}
+
+impl Error for GridError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GridError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for GridError {
+    fn from(e: io::Error) -> Self {
+        GridError::Io(e)
+    }
+}
+
+/// How to handle input where the lines aren't all the same length.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Ragged {
+    /// Return [GridError::Ragged] for the first line that doesn't match.
+    #[default]
+    Reject,
+    /// Pad short lines out to the longest line with the character.
+    Pad(char),
+}
+
+/// Structure representing a grid/map/2D array of any cell type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T = char> {
+    /// Cells of the grid in row-major order.
+    cells: Vec<T>,
+    /// Number of columns in the grid.
+    width: usize,
+    /// Number of rows in the grid.
+    height: usize,
+    /// Coordinate of the top left cell.
+    origin: Coord,
+}
+
+impl<T> Grid<T> {
     /// Make a new empty grid.
     pub fn new() -> Self {
         Self {
-            char_map: HashMap::new(),
-            max_x: 0,
-            max_y: 0,
+            cells: Vec::new(),
+            width: 0,
+            height: 0,
+            origin: Coord::new(0, 0),
         }
     }
 
-    /// Make a new grid from a String.
-    pub fn new_from_string(string: &String) -> Self {
-        let mut max_x: i32 = 0;
-        let mut max_y: i32 = 0;
-        let mut char_map: HashMap<Coord, char> = HashMap::new();
-
-        for (y, line) in string.lines().enumerate() {
-            if y as i32 > max_y {
-                max_y = y as i32;
+    /// Make a new grid of the size, with each cell made from its coordinate.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Coord) -> T) -> Self {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(f(Coord::new(x as i32, y as i32)));
             }
-            for (x, character) in line.chars().enumerate() {
-                if x as i32 > max_x {
-                    max_x = x as i32;
+        }
+        Self {
+            cells,
+            width,
+            height,
+            origin: Coord::new(0, 0),
+        }
+    }
+
+    /// Make a new grid covering the rect, with each cell made from its
+    /// coordinate.
+    pub fn from_rect(rect: Rect, mut f: impl FnMut(Coord) -> T) -> Self {
+        let (width, height) = (rect.width() as usize, rect.height() as usize);
+        Self::from_fn(width, height, |coord| f(coord + rect.min)).with_origin(rect.min)
+    }
+
+    /// Move the grid so its top left cell is at `origin`.
+    pub fn with_origin(mut self, origin: Coord) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Build the cells from the lines of a string. An empty string makes an empty
+    /// grid here, the public `try_` functions decide if that's an error.
+    fn from_lines(
+        string: &str,
+        ragged: Ragged,
+        mut parse: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, GridError> {
+        let lines: Vec<&str> = string.lines().collect();
+        let width = match ragged {
+            Ragged::Reject => lines.first().map_or(0, |line| line.chars().count()),
+            Ragged::Pad(_) => lines
+                .iter()
+                .map(|line| line.chars().count())
+                .max()
+                .unwrap_or(0),
+        };
+        let mut cells: Vec<T> = Vec::with_capacity(width * lines.len());
+
+        for (y, line) in lines.iter().enumerate() {
+            let found = line.chars().count();
+            let fill = match ragged {
+                Ragged::Pad(fill) => fill,
+                // Nothing gets padded so the fill doesn't matter.
+                Ragged::Reject if found == width => ' ',
+                Ragged::Reject => {
+                    return Err(GridError::Ragged {
+                        line: y + 1,
+                        expected: width,
+                        found,
+                    });
+                }
+            };
+            let padding = std::iter::repeat_n(fill, width - found);
+            for (x, ch) in line.chars().chain(padding).enumerate() {
+                match parse(ch) {
+                    Some(cell) => cells.push(cell),
+                    None => {
+                        return Err(GridError::InvalidChar {
+                            coord: Coord::new(x as i32, y as i32),
+                            ch,
+                        });
+                    }
                 }
-                char_map.insert(Coord::new(x as i32, y as i32), character);
             }
         }
 
-        Self {
-            char_map: char_map,
-            max_x: max_x,
-            max_y: max_y,
-        }
+        Ok(Self {
+            cells,
+            width,
+            height: lines.len(),
+            origin: Coord::new(0, 0),
+        })
     }
 
-    /// Make a new grid from a file.
-    pub fn new_from_file(file_name: &str) -> Self {
+    /// Make a new grid from a String, converting each character into a cell.
+    ///
+    /// Panics if the lines are not all the same length.
+    pub fn parse_with(string: &str, mut parse: impl FnMut(char) -> T) -> Self {
+        Self::from_lines(string, Ragged::Reject, |c| Some(parse(c)))
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Make a new grid from a file, converting each character into a cell.
+    pub fn parse_file_with(file_name: &str, parse: impl FnMut(char) -> T) -> Self {
         let file_contents = std::fs::read_to_string(file_name).expect("Couldn't open file");
-        Self::new_from_string(&file_contents)
+        Self::parse_with(&file_contents, parse)
     }
 
-    /// Set max x and y for the grid.
-    pub fn set_max_sizes(mut self) {
-        for key in self.char_map.keys() {
-            if key.x > self.max_x {
-                self.max_x = key.x;
-            }
-            if key.y > self.max_y {
-                self.max_y = key.y;
-            }
+    /// Try to make a new grid from a String, converting each character into a
+    /// cell. The parser returns None for characters that aren't allowed.
+    pub fn try_parse_with(
+        string: &str,
+        ragged: Ragged,
+        parse: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, GridError> {
+        let grid = Self::from_lines(string, ragged, parse)?;
+        if grid.is_empty() {
+            return Err(GridError::Empty);
         }
+        Ok(grid)
+    }
+
+    /// Try to make a new grid from a file, converting each character into a cell.
+    pub fn try_parse_file_with(
+        file_name: &str,
+        ragged: Ragged,
+        parse: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, GridError> {
+        let file_contents = std::fs::read_to_string(file_name)?;
+        Self::try_parse_with(&file_contents, ragged, parse)
+    }
+
+    /// Number of columns in the grid.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of rows in the grid.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Smallest x coordinate in the grid.
+    pub fn min_x(&self) -> i32 {
+        self.origin.x
+    }
+
+    /// Smallest y coordinate in the grid.
+    pub fn min_y(&self) -> i32 {
+        self.origin.y
+    }
+
+    /// Largest x coordinate in the grid. One less than `min_x` when the grid is
+    /// empty.
+    pub fn max_x(&self) -> i32 {
+        self.origin.x + self.width as i32 - 1
+    }
+
+    /// Largest y coordinate in the grid. One less than `min_y` when the grid is
+    /// empty.
+    pub fn max_y(&self) -> i32 {
+        self.origin.y + self.height as i32 - 1
+    }
+
+    /// Get the rect of coordinates the grid covers, or None if it's empty.
+    pub fn bounds(&self) -> Option<Rect> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(Rect::new(
+            self.origin,
+            Coord::new(self.max_x(), self.max_y()),
+        ))
+    }
+
+    /// Grid has no cells.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Index into the cell vec for the coordinate, if it is on the grid.
+    fn index_of(&self, coord: &Coord) -> Option<usize> {
+        // Widen so coordinates far from the origin can't overflow.
+        let x = usize::try_from(coord.x as i64 - self.origin.x as i64).ok()?;
+        let y = usize::try_from(coord.y as i64 - self.origin.y as i64).ok()?;
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        Some(y * self.width + x)
+    }
+
+    /// Coordinate for an index into the cell vec.
+    fn coord_of(&self, index: usize) -> Coord {
+        self.origin + Coord::new((index % self.width) as i32, (index / self.width) as i32)
     }
 
     /// Grid contains the coordinate.
     pub fn has_coord(&self, coord: &Coord) -> bool {
-        self.char_map.contains_key(coord)
+        self.index_of(coord).is_some()
+    }
+
+    /// Get the cell at the coordinate.
+    pub fn get(&self, coord: &Coord) -> Option<&T> {
+        self.index_of(coord).map(|i| &self.cells[i])
+    }
+
+    /// Get a mutable reference to the cell at the coordinate.
+    pub fn get_mut(&mut self, coord: &Coord) -> Option<&mut T> {
+        self.index_of(coord).map(|i| &mut self.cells[i])
+    }
+
+    /// Get a row of the grid as a slice, counting from 0 at the top whatever the
+    /// origin is.
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        if y >= self.height {
+            return None;
+        }
+        Some(&self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// Iterate the cells of a column from top to bottom, counting from 0 at the
+    /// left whatever the origin is.
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T>> {
+        if x >= self.width {
+            return None;
+        }
+        Some(self.cells[x..].iter().step_by(self.width))
+    }
+
+    /// Iterate the rows of the grid from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on 0, an empty grid has no rows anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Iterate all coordinates and cells in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (self.coord_of(i), c))
+    }
+
+    /// Iterate all coordinates in row-major order.
+    pub fn coords(&self) -> impl Iterator<Item = Coord> + '_ {
+        (0..self.cells.len()).map(|i| self.coord_of(i))
+    }
+
+    /// Make a new grid of the same size by converting every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+            origin: self.origin,
+        }
+    }
+
+    /// Make a new grid of the same size by converting every cell along with its
+    /// coordinate.
+    pub fn map_with_coord<U>(&self, mut f: impl FnMut(Coord, &T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.iter().map(|(coord, cell)| f(coord, cell)).collect(),
+            width: self.width,
+            height: self.height,
+            origin: self.origin,
+        }
+    }
+
+    /// Combine two grids of the same size cell by cell into a new grid.
+    ///
+    /// Panics if the grids are not the same size.
+    pub fn zip<U, V>(&self, other: &Grid<U>, mut f: impl FnMut(&T, &U) -> V) -> Grid<V> {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "Grids must be the same size to zip."
+        );
+        Grid {
+            cells: self
+                .cells
+                .iter()
+                .zip(other.cells.iter())
+                .map(|(a, b)| f(a, b))
+                .collect(),
+            width: self.width,
+            height: self.height,
+            origin: self.origin,
+        }
     }
 
     /// Separator on the x axis legend.
@@ -82,7 +388,12 @@ impl Grid {
 
     /// Get y legend width since we need to know it for aligning the x legend.
     fn get_y_legend_width(&self) -> usize {
-        self.max_y.to_string().len() + Self::Y_LEGEND_SEP.len()
+        Self::label_width(self.min_y(), self.max_y()) + Self::Y_LEGEND_SEP.len()
+    }
+
+    /// Length of the longest number from `min` to `max`, minus signs included.
+    fn label_width(min: i32, max: i32) -> usize {
+        min.to_string().len().max(max.to_string().len())
     }
 
     /// Make legend for x axis of the grid for display purpose.
@@ -90,83 +401,337 @@ impl Grid {
         let mut legend: String = "".to_string();
 
         // Offset each row of the legend by the y axis legend width.
-        let offset: &str = &" ".repeat(self.get_y_legend_width() as usize);
+        let offset: &str = &" ".repeat(self.get_y_legend_width());
 
-        for i in (0..self.max_x.to_string().len()).rev() {
+        for i in (0..Self::label_width(self.min_x(), self.max_x())).rev() {
             let mut line: String = offset.to_string();
-            for j in 0..self.max_x + 1 {
+            for j in self.min_x()..=self.max_x() {
                 match j.to_string().chars().rev().nth(i) {
                     None => line.push(Self::X_LEGEND_FILL),
                     Some(c) => line.push(c),
                 }
             }
-            legend.push_str("\n");
+            legend.push('\n');
             legend.push_str(&line);
         }
-        legend.push_str("\n");
+        legend.push('\n');
         // Separator line
-        let line = offset.to_string()
-            + &Self::X_LEGEND_SEP
-                .to_string()
-                .repeat(self.max_x as usize + 1)
-            + "\n";
+        let line = offset.to_string() + &Self::X_LEGEND_SEP.to_string().repeat(self.width) + "\n";
         legend.push_str(&line);
         cyan(&legend)
         // legend
     }
-}
 
-impl fmt::Display for Grid {
-    /// Format the grid to print out nicely with a legend and colors.
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let width = self.max_y.to_string().len();
+    /// Start a view of the grid that colors some of the cells when displayed.
+    pub fn highlighted(&self) -> Highlighted<'_, T> {
+        Highlighted {
+            grid: self,
+            layers: Vec::new(),
+        }
+    }
+
+    /// Write the legend and cells, styling each cell with what `style` gives for
+    /// its coordinate.
+    fn write_cells(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        style: impl Fn(Coord) -> Option<Style>,
+    ) -> fmt::Result
+    where
+        T: fmt::Display,
+    {
+        let width = Self::label_width(self.min_y(), self.max_y());
         write!(f, "{}", self.x_legend())?;
-        for y in 0..self.max_y + 1 {
+        for (y, row) in self.rows().enumerate() {
+            let y = self.min_y() + y as i32;
             let y_legend: String = cyan(&format!("{:>width$}{}", y, Self::Y_LEGEND_SEP));
             write!(f, "{y_legend}")?;
-            for x in 0..self.max_x + 1 {
-                write!(
-                    f,
-                    "{}",
-                    self.char_map
-                        .get(&Coord::new(x, y))
-                        .expect("Didn't find char to print.")
-                )?;
+            for (x, c) in row.iter().enumerate() {
+                match style(Coord::new(self.min_x() + x as i32, y)) {
+                    Some(style) => write!(f, "{}", style(&c.to_string()))?,
+                    None => write!(f, "{c}")?,
+                }
             }
-            if y < self.max_y {
-                write!(f, "\n")?
+            if y < self.max_y() {
+                writeln!(f)?
             }
         }
         Ok(())
     }
 }
 
+/// How to color a cell, like the functions in [crate::color_text].
+pub type Style = fn(&str) -> String;
+
+/// View of a grid that displays with layers of cells colored, for seeing which
+/// cells a solution picked. Made by [Grid::highlighted].
+#[derive(Clone, Debug)]
+pub struct Highlighted<'a, T> {
+    grid: &'a Grid<T>,
+    layers: Vec<(HashSet<Coord>, Style)>,
+}
+
+impl<T> Highlighted<'_, T> {
+    /// Add a layer coloring the cells with the style. Later layers are drawn
+    /// over earlier ones.
+    pub fn layer(mut self, coords: impl IntoIterator<Item = Coord>, style: Style) -> Self {
+        self.layers.push((coords.into_iter().collect(), style));
+        self
+    }
+
+    /// Get the style of the top layer covering the coordinate.
+    fn style(&self, coord: Coord) -> Option<Style> {
+        self.layers
+            .iter()
+            .rev()
+            .find(|(coords, _)| coords.contains(&coord))
+            .map(|(_, style)| *style)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Highlighted<'_, T> {
+    /// Format the grid like the grid itself, with the layers colored.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.grid.write_cells(f, |coord| self.style(coord))
+    }
+}
+
+impl Grid<char> {
+    /// Make a new grid of characters from a String.
+    ///
+    /// Panics if the lines are not all the same length.
+    pub fn new_from_string(string: &str) -> Self {
+        Self::parse_with(string, |c| c)
+    }
+
+    /// Make a new grid of characters from a file.
+    pub fn new_from_file(file_name: &str) -> Self {
+        Self::parse_file_with(file_name, |c| c)
+    }
+
+    /// Try to make a new grid of characters from a String. Lines must all be the
+    /// same length.
+    pub fn try_from_str(string: &str) -> Result<Self, GridError> {
+        Self::try_parse_with(string, Ragged::Reject, Some)
+    }
+
+    /// Try to make a new grid of characters from a file. Lines must all be the
+    /// same length.
+    pub fn try_from_file(file_name: &str) -> Result<Self, GridError> {
+        Self::try_parse_file_with(file_name, Ragged::Reject, Some)
+    }
+}
+
+impl<T> Default for Grid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    /// Get the cell at the coordinate. Panics if it is off the grid.
+    fn index(&self, coord: Coord) -> &T {
+        self.get(&coord)
+            .unwrap_or_else(|| panic!("{coord} is not on the grid."))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    /// Get the cell at the coordinate mutably. Panics if it is off the grid.
+    fn index_mut(&mut self, coord: Coord) -> &mut T {
+        self.get_mut(&coord)
+            .unwrap_or_else(|| panic!("{coord} is not on the grid."))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    /// Format the grid to print out nicely with a legend and colors.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_cells(f, |_| None)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_new_grid() {
-        let grid = Grid::new();
-        assert!(grid.char_map.is_empty());
-        assert_eq!(grid.max_x, 0);
-        assert_eq!(grid.max_y, 0);
+        let grid: Grid = Grid::new();
+        assert!(grid.is_empty());
+        assert_eq!(grid.width(), 0);
+        assert_eq!(grid.height(), 0);
     }
 
     #[test]
     fn test_new_grid_from_string() {
         let string: String = "123\n456".to_string();
         let grid = Grid::new_from_string(&string);
-        assert_eq!(grid.max_x, 2);
-        assert_eq!(grid.max_y, 1);
-        assert_eq!(grid.char_map.get(&Coord::new(0, 2)), None);
-        assert_eq!(grid.char_map.get(&Coord::new(0, 0)), Some(&'1'));
-        assert_eq!(grid.char_map.get(&Coord::new(1, 0)), Some(&'2'));
-        assert_eq!(grid.char_map.get(&Coord::new(2, 0)), Some(&'3'));
-        assert_eq!(grid.char_map.get(&Coord::new(0, 1)), Some(&'4'));
-        assert_eq!(grid.char_map.get(&Coord::new(1, 1)), Some(&'5'));
-        assert_eq!(grid.char_map.get(&Coord::new(2, 1)), Some(&'6'));
-        assert_eq!(grid.char_map.get(&Coord::new(3, 1)), None);
+        assert_eq!(grid.max_x(), 2);
+        assert_eq!(grid.max_y(), 1);
+        assert_eq!(grid.get(&Coord::new(0, 2)), None);
+        assert_eq!(grid.get(&Coord::new(0, 0)), Some(&'1'));
+        assert_eq!(grid.get(&Coord::new(1, 0)), Some(&'2'));
+        assert_eq!(grid.get(&Coord::new(2, 0)), Some(&'3'));
+        assert_eq!(grid.get(&Coord::new(0, 1)), Some(&'4'));
+        assert_eq!(grid.get(&Coord::new(1, 1)), Some(&'5'));
+        assert_eq!(grid.get(&Coord::new(2, 1)), Some(&'6'));
+        assert_eq!(grid.get(&Coord::new(3, 1)), None);
+        assert_eq!(grid.get(&Coord::new(-1, 0)), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_grid_from_ragged_string() {
+        Grid::new_from_string("123\n45");
+    }
+
+    #[test]
+    fn test_try_from_str_errors() {
+        assert!(matches!(
+            Grid::try_from_str("123\n45\n6789"),
+            Err(GridError::Ragged {
+                line: 2,
+                expected: 3,
+                found: 2
+            })
+        ));
+        assert!(matches!(Grid::try_from_str(""), Err(GridError::Empty)));
+        assert!(matches!(
+            Grid::try_from_file("does_not_exist.txt"),
+            Err(GridError::Io(_))
+        ));
+        let invalid = Grid::try_parse_with("12\n3x", Ragged::Reject, |c| c.to_digit(10));
+        assert!(matches!(
+            invalid,
+            Err(GridError::InvalidChar {
+                coord: Coord { x: 1, y: 1 },
+                ch: 'x'
+            })
+        ));
+    }
+
+    #[test]
+    fn test_try_parse_with_padding() {
+        let grid = Grid::try_parse_with("123\n4\n56", Ragged::Pad('.'), Some).unwrap();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid.row(1), Some(&['4', '.', '.'][..]));
+        assert_eq!(grid.row(2), Some(&['5', '6', '.'][..]));
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::new_from_string("123\n456");
+        assert_eq!(grid.row(1), Some(&['4', '5', '6'][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(
+            grid.column(1).unwrap().copied().collect::<Vec<char>>(),
+            vec!['2', '5']
+        );
+        assert!(grid.column(3).is_none());
+        assert_eq!(grid.rows().count(), 2);
+    }
+
+    #[test]
+    fn test_iter_is_row_major() {
+        let grid = Grid::new_from_string("12\n34");
+        let cells: Vec<(Coord, char)> = grid.iter().map(|(c, v)| (c, *v)).collect();
+        assert_eq!(
+            cells,
+            vec![
+                (Coord::new(0, 0), '1'),
+                (Coord::new(1, 0), '2'),
+                (Coord::new(0, 1), '3'),
+                (Coord::new(1, 1), '4'),
+            ]
+        );
+        assert_eq!(grid[Coord::new(1, 1)], '4');
+    }
+
+    #[test]
+    fn test_parse_with_and_map() {
+        let grid = Grid::parse_with("1.\n.2", |c| c.to_digit(10));
+        assert_eq!(grid[Coord::new(0, 0)], Some(1));
+        assert_eq!(grid[Coord::new(1, 0)], None);
+        let doubled = grid.map(|cell| cell.map_or(0, |d| d * 2));
+        assert_eq!(doubled[Coord::new(1, 1)], 4);
+        let xs = grid.map_with_coord(|coord, _| coord.x);
+        assert_eq!(xs.row(1), Some(&[0, 1][..]));
+        let zipped = doubled.zip(&xs, |d, x| d + *x as u32);
+        assert_eq!(zipped.row(0), Some(&[2, 1][..]));
+        assert_eq!(zipped.row(1), Some(&[0, 5][..]));
+    }
+
+    #[test]
+    fn test_from_fn() {
+        let grid = Grid::from_fn(3, 2, |coord| coord.x + coord.y * 10);
+        assert_eq!(grid.row(0), Some(&[0, 1, 2][..]));
+        assert_eq!(grid.row(1), Some(&[10, 11, 12][..]));
+        assert!(Grid::from_fn(0, 0, |_| 0).is_empty());
+    }
+
+    #[test]
+    fn test_display_non_char_cells() {
+        let grid = Grid::parse_with("12", |c| c.to_digit(10).unwrap() + 1);
+        assert!(grid.to_string().ends_with("23"));
+    }
+
+    #[test]
+    fn test_highlighted() {
+        use crate::color_text::{green, red};
+
+        let grid = Grid::new_from_string("ab\ncd");
+        let plain = grid.to_string();
+        assert_eq!(grid.highlighted().to_string(), plain);
+
+        let highlighted = grid
+            .highlighted()
+            .layer([Coord::new(0, 0), Coord::new(1, 1)], green)
+            .layer([Coord::new(1, 1), Coord::new(5, 5)], red)
+            .to_string();
+        let rows: Vec<&str> = highlighted.lines().collect();
+        assert!(rows[rows.len() - 2].ends_with(&format!("{}b", green("a"))));
+        assert!(rows[rows.len() - 1].ends_with(&format!("c{}", red("d"))));
+    }
+
+    #[test]
+    fn test_negative_origin() {
+        let grid = Grid::new_from_string("ab\ncd").with_origin(Coord::new(-1, -1));
+        assert_eq!(
+            grid.bounds(),
+            Some(Rect::new(Coord::new(-1, -1), Coord::new(0, 0)))
+        );
+        assert_eq!(grid[Coord::new(-1, -1)], 'a');
+        assert_eq!(grid[Coord::new(0, 0)], 'd');
+        assert_eq!(grid.get(&Coord::new(1, 0)), None);
+        assert_eq!(grid.get(&Coord::new(i32::MIN, i32::MAX)), None);
+        assert_eq!(
+            grid.coords().collect::<Vec<Coord>>(),
+            grid.bounds().unwrap().coords().collect::<Vec<Coord>>()
+        );
+        let shifted = grid.map_with_coord(|coord, _| coord);
+        assert_eq!(shifted[Coord::new(0, -1)], Coord::new(0, -1));
+
+        let text = grid.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        // Column labels are written downwards with the units on the last line.
+        assert!(lines[1].ends_with(" - "));
+        assert!(lines[2].ends_with(" 10"));
+        assert!(lines[lines.len() - 2].ends_with(&format!("{}ab", cyan("-1 - "))));
+        assert!(lines[lines.len() - 1].ends_with(&format!("{}cd", cyan(" 0 - "))));
+        assert_eq!(Grid::<char>::new().bounds(), None);
+    }
+
+    #[test]
+    fn test_from_rect() {
+        let rect = Rect::new(Coord::new(-2, 3), Coord::new(0, 4));
+        let grid = Grid::from_rect(rect, |coord| coord.x * coord.y);
+        assert_eq!(grid.bounds(), Some(rect));
+        assert_eq!(grid[Coord::new(-2, 4)], -8);
+        assert_eq!(grid.row(0), Some(&[-6, -3, 0][..]));
     }
 
     #[test]
@@ -0,0 +1,259 @@
+//! Puzzle inputs, kept in a local cache and downloaded when they're missing.
+//!
+//! Inputs live in `~/.cache/aoc/<year>/day<NN>.txt`, or under `AOC_CACHE` if it's
+//! set. Missing inputs are fetched from `AOC_BASE_URL` (the real site unless
+//! set) using the session cookie in `AOC_SESSION`.
+
+use std::{
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Year all the puzzles in this repository are from.
+pub const YEAR: u16 = 2023;
+
+/// Where inputs are downloaded from by default.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Error getting an input.
+#[derive(Debug)]
+pub enum FetchError {
+    /// Reading or writing the cache failed.
+    Io(io::Error),
+    /// Neither `AOC_CACHE` nor `HOME` is set, so there's nowhere to cache inputs.
+    NoCacheDir,
+    /// Input isn't cached and there's no session token to download it with.
+    NoSession,
+    /// Download failed.
+    Http { url: String, message: String },
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Io(e) => write!(f, "{e}"),
+            FetchError::NoCacheDir => write!(f, "set AOC_CACHE or HOME to cache inputs"),
+            FetchError::NoSession => {
+                write!(f, "input isn't cached; set AOC_SESSION to download it")
+            }
+            FetchError::Http { url, message } => write!(f, "couldn't download {url}: {message}"),
+        }
+    }
+}
+
+impl Error for FetchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FetchError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for FetchError {
+    fn from(e: io::Error) -> Self {
+        FetchError::Io(e)
+    }
+}
+
+/// Where inputs are cached and how to download them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Inputs {
+    /// Directory holding one directory per year.
+    pub cache_dir: PathBuf,
+    /// Site to download from, without a trailing slash.
+    pub base_url: String,
+    /// Session cookie value for downloading.
+    pub session: Option<String>,
+}
+
+impl Inputs {
+    /// Make a new input cache in `cache_dir`, downloading from the real site.
+    pub fn new(cache_dir: impl Into<PathBuf>, session: Option<String>) -> Self {
+        Self {
+            cache_dir: cache_dir.into(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session,
+        }
+    }
+
+    /// Settings from `AOC_CACHE`, `AOC_BASE_URL` and `AOC_SESSION`.
+    pub fn from_env() -> Result<Self, FetchError> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let cache_dir = match (var("AOC_CACHE"), var("HOME")) {
+            (Some(dir), _) => PathBuf::from(dir),
+            (None, Some(home)) => Path::new(&home).join(".cache").join("aoc"),
+            (None, None) => return Err(FetchError::NoCacheDir),
+        };
+        let mut inputs = Self::new(cache_dir, var("AOC_SESSION").map(|s| s.trim().to_string()));
+        if let Some(url) = var("AOC_BASE_URL") {
+            inputs.base_url = url.trim_end_matches('/').to_string();
+        }
+        Ok(inputs)
+    }
+
+    /// Get the cache file for a day.
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.cache_dir
+            .join(year.to_string())
+            .join(format!("day{day:02}.txt"))
+    }
+
+    /// Get the URL a day's input is downloaded from.
+    pub fn url(&self, year: u16, day: u8) -> String {
+        format!("{}/{year}/day/{day}/input", self.base_url)
+    }
+
+    /// Get a day's input from the cache, downloading and caching it if it's
+    /// not there yet.
+    pub fn load(&self, year: u16, day: u8) -> Result<String, FetchError> {
+        let path = self.path(year, day);
+        match fs::read_to_string(&path) {
+            Ok(input) => return Ok(input),
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+            Err(_) => {}
+        }
+        let input = self.fetch(year, day)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, &input)?;
+        Ok(input)
+    }
+
+    /// Download a day's input without touching the cache.
+    pub fn fetch(&self, year: u16, day: u8) -> Result<String, FetchError> {
+        let session = self.session.as_ref().ok_or(FetchError::NoSession)?;
+        let url = self.url(year, day);
+        let http_error = |message: String| FetchError::Http {
+            url: url.clone(),
+            message,
+        };
+        let mut response = ureq::get(&url)
+            .header("Cookie", &format!("session={session}"))
+            .header("User-Agent", "github.com/Ac5000/2023-advent-of-code")
+            .call()
+            .map_err(|e| http_error(e.to_string()))?;
+        let input = response
+            .body_mut()
+            .read_to_string()
+            .map_err(|e| http_error(e.to_string()))?;
+        if input.trim().is_empty() {
+            return Err(http_error("input was empty".to_string()));
+        }
+        Ok(input)
+    }
+}
+
+/// Get a day's input for [YEAR] using settings from the environment.
+pub fn load(day: u8) -> Result<String, FetchError> {
+    Inputs::from_env()?.load(YEAR, day)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Stand-in server answering `responses.len()` requests in order with the
+    /// given status and body. Joining the handle gives the raw requests.
+    pub(crate) fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some((name, value)) = line.split_once(':')
+                        && name.eq_ignore_ascii_case("content-length")
+                    {
+                        length = value.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                }
+                let mut content = vec![0; length];
+                io::Read::read_exact(&mut reader, &mut content).unwrap();
+                request.push_str(&String::from_utf8(content).unwrap());
+                requests.push(request);
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    /// Empty directory for a test to use as its cache.
+    pub(crate) fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_path_and_url() {
+        let mut inputs = Inputs::new("/cache", None);
+        assert_eq!(inputs.path(2023, 5), PathBuf::from("/cache/2023/day05.txt"));
+        assert_eq!(
+            inputs.url(2023, 5),
+            "https://adventofcode.com/2023/day/5/input"
+        );
+        inputs.base_url = "http://127.0.0.1:8080".to_string();
+        assert_eq!(
+            inputs.url(2023, 12),
+            "http://127.0.0.1:8080/2023/day/12/input"
+        );
+    }
+
+    #[test]
+    fn test_load_fetches_once_then_uses_cache() {
+        let (url, server) = serve(vec![(200, "1 2 3\n")]);
+        let mut inputs = Inputs::new(temp_dir("fetch"), Some("abc".to_string()));
+        inputs.base_url = url;
+
+        assert_eq!(inputs.load(2023, 5).unwrap(), "1 2 3\n");
+        assert_eq!(inputs.load(2023, 5).unwrap(), "1 2 3\n");
+        assert_eq!(fs::read_to_string(inputs.path(2023, 5)).unwrap(), "1 2 3\n");
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2023/day/5/input HTTP/1.1"));
+        assert!(requests[0].to_lowercase().contains("cookie: session=abc"));
+        fs::remove_dir_all(&inputs.cache_dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_errors() {
+        let (url, server) = serve(vec![(404, "Not found")]);
+        let mut inputs = Inputs::new(temp_dir("errors"), Some("abc".to_string()));
+        inputs.base_url = url;
+        assert!(matches!(inputs.load(2023, 6), Err(FetchError::Http { .. })));
+        assert!(!inputs.path(2023, 6).exists());
+        server.join().unwrap();
+
+        inputs.session = None;
+        assert!(matches!(inputs.load(2023, 6), Err(FetchError::NoSession)));
+        fs::remove_dir_all(&inputs.cache_dir).unwrap();
+    }
+}
@@ -0,0 +1,438 @@
+//! Module for half-open intervals, sets of them, and maps that shift ranges of
+//! numbers around. Useful when the inputs are too big to go one number at a time.
+
+use std::fmt;
+
+/// Half-open interval `[start, end)`. Empty when `start >= end`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Interval<T = i64> {
+    /// First value in the interval.
+    pub start: T,
+    /// First value after the interval.
+    pub end: T,
+}
+
+impl<T: Copy + Ord> Interval<T> {
+    /// Make a new interval from `start` up to but not including `end`.
+    pub const fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    /// Interval has no values in it.
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    /// Interval contains the value.
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// Intervals share at least one value.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.intersect(other).is_empty()
+    }
+
+    /// Values in both intervals. Can be empty.
+    pub fn intersect(&self, other: &Self) -> Self {
+        Self::new(self.start.max(other.start), self.end.min(other.end))
+    }
+}
+
+impl Interval<i64> {
+    /// Make a new interval of `len` values starting at `start`.
+    pub const fn from_len(start: i64, len: i64) -> Self {
+        Self::new(start, start + len)
+    }
+
+    /// Number of values in the interval.
+    pub fn len(&self) -> i64 {
+        (self.end - self.start).max(0)
+    }
+
+    /// Move the interval by `offset`.
+    pub const fn shift(&self, offset: i64) -> Self {
+        Self::new(self.start + offset, self.end + offset)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Interval<T> {
+    /// Format the interval in half-open notation.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+/// Set of values stored as sorted, non-overlapping, non-touching intervals.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IntervalSet<T = i64> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    /// Make a new empty set.
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    /// Add the values of an interval to the set.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        // Everything that overlaps or touches the new interval gets merged into it.
+        let first = self.intervals.partition_point(|i| i.end < interval.start);
+        let last = self.intervals.partition_point(|i| i.start <= interval.end);
+        let mut merged = interval;
+        if first < last {
+            merged.start = merged.start.min(self.intervals[first].start);
+            merged.end = merged.end.max(self.intervals[last - 1].end);
+        }
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    /// Sorted intervals that make up the set.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    /// Iterate the intervals in order.
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+
+    /// Set has no values.
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Set contains the value.
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals
+            .get(index)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    /// Smallest value in the set.
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    /// Values in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        for interval in &other.intervals {
+            set.insert(*interval);
+        }
+        set
+    }
+
+    /// Values in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut set = Self::new();
+        let (mut a, mut b) = (0, 0);
+        while a < self.intervals.len() && b < other.intervals.len() {
+            let (left, right) = (self.intervals[a], other.intervals[b]);
+            set.insert(left.intersect(&right));
+            if left.end < right.end {
+                a += 1;
+            } else {
+                b += 1;
+            }
+        }
+        set
+    }
+
+    /// Values in this set that aren't in the other.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut set = Self::new();
+        for interval in &self.intervals {
+            let mut start = interval.start;
+            for cut in other.iter().filter(|cut| cut.overlaps(interval)) {
+                set.insert(Interval::new(start, cut.start));
+                start = start.max(cut.end);
+            }
+            set.insert(Interval::new(start, interval.end));
+        }
+        set
+    }
+}
+
+impl IntervalSet<i64> {
+    /// Total number of values in the set.
+    pub fn len(&self) -> i64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+/// A rule moving every value in `source` by `offset`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Rule {
+    pub source: Interval<i64>,
+    pub offset: i64,
+}
+
+/// Piecewise map of numbers. Values inside a rule's source are moved by the rule's
+/// offset, everything else maps to itself.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RangeMap {
+    /// Rules sorted by source start. Sources never overlap.
+    rules: Vec<Rule>,
+}
+
+impl RangeMap {
+    /// Make a new map where every value maps to itself.
+    pub fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Make a new map from `(destination, source, length)` rules.
+    ///
+    /// Panics if the source ranges overlap.
+    pub fn from_rules(rules: impl IntoIterator<Item = (i64, i64, i64)>) -> Self {
+        let mut map = Self::new();
+        for (dest, source, len) in rules {
+            map.add_rule(dest, source, len);
+        }
+        map
+    }
+
+    /// Map `len` values starting at `source` onto values starting at `dest`.
+    ///
+    /// Panics if the source range overlaps an existing rule.
+    pub fn add_rule(&mut self, dest: i64, source: i64, len: i64) {
+        if let Err(existing) = self.try_add_rule(dest, source, len) {
+            panic!(
+                "Rule for {} overlaps an existing rule for {existing}.",
+                Interval::from_len(source, len)
+            );
+        }
+    }
+
+    /// Map `len` values starting at `source` onto values starting at `dest`, or
+    /// give back the source of the existing rule it overlaps.
+    pub fn try_add_rule(&mut self, dest: i64, source: i64, len: i64) -> Result<(), Interval<i64>> {
+        let rule = Rule {
+            source: Interval::from_len(source, len),
+            offset: dest - source,
+        };
+        if rule.source.is_empty() {
+            return Ok(());
+        }
+        let index = self
+            .rules
+            .partition_point(|r| r.source.start < rule.source.start);
+        let previous = index.checked_sub(1).and_then(|i| self.rules.get(i));
+        let overlapping = [previous, self.rules.get(index)]
+            .into_iter()
+            .flatten()
+            .find(|r| r.source.overlaps(&rule.source));
+        if let Some(existing) = overlapping {
+            return Err(existing.source);
+        }
+        self.rules.insert(index, rule);
+        Ok(())
+    }
+
+    /// Rules sorted by source start.
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Map a single value.
+    pub fn map(&self, value: i64) -> i64 {
+        let index = self.rules.partition_point(|r| r.source.end <= value);
+        match self.rules.get(index) {
+            Some(rule) if rule.source.contains(value) => value + rule.offset,
+            _ => value,
+        }
+    }
+
+    /// Map a whole interval, splitting it wherever it crosses a rule boundary.
+    pub fn map_interval(&self, interval: Interval<i64>) -> Vec<Interval<i64>> {
+        let mut mapped = Vec::new();
+        let mut start = interval.start;
+        for rule in self.rules.iter().filter(|r| r.source.overlaps(&interval)) {
+            let before = Interval::new(start, rule.source.start);
+            if !before.is_empty() {
+                mapped.push(before);
+            }
+            let inside = rule.source.intersect(&interval);
+            mapped.push(inside.shift(rule.offset));
+            start = inside.end;
+        }
+        let after = Interval::new(start, interval.end);
+        if !after.is_empty() {
+            mapped.push(after);
+        }
+        mapped
+    }
+
+    /// Map every value of the set.
+    pub fn map_set(&self, set: &IntervalSet<i64>) -> IntervalSet<i64> {
+        set.iter()
+            .flat_map(|interval| self.map_interval(*interval))
+            .collect()
+    }
+
+    /// Make one map that does this map and then `next`.
+    pub fn then(&self, next: &RangeMap) -> RangeMap {
+        // Between two neighbouring breakpoints both maps shift by a single
+        // offset, so one rule covers each piece.
+        let mut breakpoints: Vec<i64> = Vec::new();
+        for rule in &self.rules {
+            breakpoints.extend([rule.source.start, rule.source.end]);
+        }
+        for boundary in next
+            .rules
+            .iter()
+            .flat_map(|r| [r.source.start, r.source.end])
+        {
+            // Every value that this map sends onto the boundary.
+            if self.map(boundary) == boundary {
+                breakpoints.push(boundary);
+            }
+            for rule in &self.rules {
+                if rule.source.contains(boundary - rule.offset) {
+                    breakpoints.push(boundary - rule.offset);
+                }
+            }
+        }
+        breakpoints.sort();
+        breakpoints.dedup();
+
+        let mut composed = RangeMap::new();
+        for pair in breakpoints.windows(2) {
+            let offset = next.map(self.map(pair[0])) - pair[0];
+            if offset == 0 {
+                continue;
+            }
+            match composed.rules.last_mut() {
+                Some(last) if last.source.end == pair[0] && last.offset == offset => {
+                    last.source.end = pair[1];
+                }
+                _ => composed.rules.push(Rule {
+                    source: Interval::new(pair[0], pair[1]),
+                    offset,
+                }),
+            }
+        }
+        composed
+    }
+
+    /// Make one map that does every map in order.
+    pub fn compose<'a>(maps: impl IntoIterator<Item = &'a RangeMap>) -> RangeMap {
+        maps.into_iter()
+            .fold(RangeMap::new(), |composed, next| composed.then(next))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet {
+        intervals
+            .iter()
+            .map(|(start, end)| Interval::new(*start, *end))
+            .collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let interval = Interval::from_len(5, 3);
+        assert_eq!(interval, Interval::new(5, 8));
+        assert_eq!(interval.len(), 3);
+        assert!(interval.contains(5));
+        assert!(!interval.contains(8));
+        assert!(interval.intersect(&Interval::new(8, 10)).is_empty());
+        assert_eq!(
+            interval.intersect(&Interval::new(7, 10)),
+            Interval::new(7, 8)
+        );
+        assert_eq!(interval.to_string(), "[5, 8)");
+    }
+
+    #[test]
+    fn test_interval_set_insert_merges() {
+        let set = set(&[(10, 12), (1, 3), (3, 5), (20, 25), (11, 21)]);
+        assert_eq!(
+            set.intervals(),
+            &[Interval::new(1, 5), Interval::new(10, 25)]
+        );
+        assert_eq!(set.len(), 19);
+        assert!(set.contains(4));
+        assert!(!set.contains(5));
+        assert_eq!(set.min(), Some(1));
+    }
+
+    #[test]
+    fn test_interval_set_operations() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25), (40, 50)]);
+        assert_eq!(a.union(&b), set(&[(0, 30), (40, 50)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25)]));
+        assert_eq!(a.difference(&b), set(&[(0, 5), (25, 30)]));
+        assert_eq!(b.difference(&a), set(&[(10, 20), (40, 50)]));
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn test_range_map() {
+        // Seed to soil map from day 05's example.
+        let map = RangeMap::from_rules([(50, 98, 2), (52, 50, 48)]);
+        assert_eq!(map.map(79), 81);
+        assert_eq!(map.map(98), 50);
+        assert_eq!(map.map(13), 13);
+        assert_eq!(
+            map.map_interval(Interval::new(40, 100)),
+            vec![
+                Interval::new(40, 50),
+                Interval::new(52, 100),
+                Interval::new(50, 52)
+            ]
+        );
+        assert_eq!(map.map_set(&set(&[(40, 102)])), set(&[(40, 102)]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_range_map_overlapping_rules() {
+        RangeMap::from_rules([(0, 10, 5), (100, 12, 5)]);
+    }
+
+    #[test]
+    fn test_range_map_try_add_rule() {
+        let mut map = RangeMap::from_rules([(0, 10, 5)]);
+        assert_eq!(map.try_add_rule(100, 12, 5), Err(Interval::from_len(10, 5)));
+        assert_eq!(map.try_add_rule(100, 15, 5), Ok(()));
+        assert_eq!(map.rules().len(), 2);
+    }
+
+    #[test]
+    fn test_range_map_compose() {
+        let first = RangeMap::from_rules([(50, 98, 2), (52, 50, 48)]);
+        let second = RangeMap::from_rules([(0, 15, 37), (37, 52, 2), (39, 0, 15)]);
+        let composed = first.then(&second);
+        for value in -5..120 {
+            assert_eq!(composed.map(value), second.map(first.map(value)), "{value}");
+        }
+        let all = RangeMap::compose([&first, &second, &RangeMap::new()]);
+        assert_eq!(all, composed);
+        let spread = composed.map_set(&set(&[(0, 110)]));
+        assert_eq!(spread.len(), 110);
+    }
+}
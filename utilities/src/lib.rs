@@ -1,5 +1,18 @@
 //! Various utility modules of code I will likely reuse.
 
+pub mod answers;
 pub mod color_text;
 pub mod coord;
+pub mod direction;
+pub mod examples;
 pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod parse;
+pub mod polygon;
+pub mod rect;
+pub mod search;
+pub mod solution;
+pub mod text;
+pub mod tiled;
+pub mod voxel;
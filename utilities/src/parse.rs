@@ -0,0 +1,472 @@
+//! Small parsing combinators for puzzle input. A [Cursor] walks through the text
+//! and every step either returns a typed value or an [InputError] saying which
+//! line and column the input stopped making sense at.
+
+use std::{error::Error, fmt, str::FromStr};
+
+/// Error in the puzzle input. Line and column both start at 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for InputError {}
+
+/// Position in some text being parsed. Cheap to copy, so trying something and
+/// going back is just keeping the old cursor.
+#[derive(Clone, Copy, Debug)]
+pub struct Cursor<'a> {
+    /// All of the text.
+    text: &'a str,
+    /// Byte offset of the next thing to parse.
+    pos: usize,
+    /// Line number of the start of `text`.
+    first_line: usize,
+}
+
+impl<'a> Cursor<'a> {
+    /// Start parsing at the beginning of the text.
+    pub fn new(text: &'a str) -> Self {
+        Self::on_line(text, 1)
+    }
+
+    /// Start parsing text that begins on `line` of the whole input, so errors
+    /// point at the right place.
+    pub fn on_line(text: &'a str, line: usize) -> Self {
+        Self {
+            text,
+            pos: 0,
+            first_line: line,
+        }
+    }
+
+    /// Text that hasn't been parsed yet.
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    /// Nothing left to parse, not even whitespace.
+    pub fn is_empty(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    /// Make an error at the current position.
+    pub fn error(&self, message: impl Into<String>) -> InputError {
+        let before = &self.text[..self.pos];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        InputError {
+            line: self.first_line + before.matches('\n').count(),
+            column: before[line_start..].chars().count() + 1,
+            message: message.into(),
+        }
+    }
+
+    /// Move past leading whitespace.
+    pub fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Take the longest run of characters matching `matches`.
+    fn take_while(&mut self, mut matches: impl FnMut(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !matches(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    /// Match exact text, after skipping whitespace.
+    pub fn literal(&mut self, literal: &str) -> Result<(), InputError> {
+        self.skip_whitespace();
+        if !self.rest().starts_with(literal) {
+            return Err(self.error(format!("expected '{literal}'")));
+        }
+        self.pos += literal.len();
+        Ok(())
+    }
+
+    /// Parse a number from digits with `from_str`, reporting errors at `start`.
+    fn number<T>(&mut self, start: Cursor<'a>) -> Result<T, InputError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let digits = &self.text[start.pos..self.pos];
+        digits
+            .parse()
+            .map_err(|e| start.error(format!("couldn't read '{digits}' as a number: {e}")))
+    }
+
+    /// Parse a number with no sign, after skipping whitespace.
+    pub fn unsigned<T>(&mut self) -> Result<T, InputError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.skip_whitespace();
+        let start = *self;
+        if self.take_while(|c| c.is_ascii_digit()).is_empty() {
+            return Err(self.error("expected a number"));
+        }
+        self.number(start)
+    }
+
+    /// Parse a number with an optional `+` or `-` sign, after skipping whitespace.
+    pub fn signed<T>(&mut self) -> Result<T, InputError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.skip_whitespace();
+        let start = *self;
+        if self.rest().starts_with(['-', '+']) {
+            self.pos += 1;
+        }
+        if self.take_while(|c| c.is_ascii_digit()).is_empty() {
+            *self = start;
+            return Err(self.error("expected a number"));
+        }
+        self.number(start)
+    }
+
+    /// Parse a word of letters, digits or underscores, after skipping whitespace.
+    pub fn word(&mut self) -> Result<&'a str, InputError> {
+        self.skip_whitespace();
+        let word = self.take_while(|c| c.is_alphanumeric() || c == '_');
+        if word.is_empty() {
+            return Err(self.error("expected a word"));
+        }
+        Ok(word)
+    }
+
+    /// Parse a double quoted string, after skipping whitespace. Supports `\"` and
+    /// `\\` escapes.
+    pub fn quoted(&mut self) -> Result<String, InputError> {
+        self.skip_whitespace();
+        let start = *self;
+        self.literal("\"")?;
+        let mut text = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(text);
+                }
+                '\\' => match chars.next() {
+                    Some((_, c @ ('"' | '\\'))) => text.push(c),
+                    _ => {
+                        self.pos += i;
+                        return Err(self.error("expected '\\\"' or '\\\\' after '\\'"));
+                    }
+                },
+                c => text.push(c),
+            }
+        }
+        Err(start.error("missing closing '\"'"))
+    }
+
+    /// Parse items separated by whitespace until one doesn't parse. The cursor is
+    /// left after the last item that did.
+    pub fn list<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<T, InputError>,
+    ) -> Result<Vec<T>, InputError> {
+        let mut items = Vec::new();
+        loop {
+            let mut attempt = *self;
+            match item(&mut attempt) {
+                Ok(value) => {
+                    items.push(value);
+                    *self = attempt;
+                }
+                Err(_) => return Ok(items),
+            }
+        }
+    }
+
+    /// Parse one or more items with `separator` between them.
+    pub fn separated_by<T>(
+        &mut self,
+        separator: &str,
+        mut item: impl FnMut(&mut Self) -> Result<T, InputError>,
+    ) -> Result<Vec<T>, InputError> {
+        let mut items = vec![item(self)?];
+        loop {
+            let mut attempt = *self;
+            if attempt.literal(separator).is_err() {
+                return Ok(items);
+            }
+            items.push(item(&mut attempt)?);
+            *self = attempt;
+        }
+    }
+
+    /// Parse a key, the separator, then a value.
+    pub fn key_value<K, V>(
+        &mut self,
+        key: impl FnOnce(&mut Self) -> Result<K, InputError>,
+        separator: &str,
+        value: impl FnOnce(&mut Self) -> Result<V, InputError>,
+    ) -> Result<(K, V), InputError> {
+        let key = key(self)?;
+        self.literal(separator)?;
+        Ok((key, value(self)?))
+    }
+
+    /// Make a cursor for every line of the rest of the text, each knowing its line
+    /// number.
+    pub fn lines(&self) -> impl Iterator<Item = Cursor<'a>> + use<'a> {
+        let line = self.first_line + self.text[..self.pos].matches('\n').count();
+        self.rest()
+            .lines()
+            .enumerate()
+            .map(move |(i, text)| Cursor::on_line(text, line + i))
+    }
+
+    /// Make sure nothing but whitespace is left.
+    pub fn end(&mut self) -> Result<(), InputError> {
+        self.skip_whitespace();
+        if !self.is_empty() {
+            return Err(self.error(format!("unexpected '{}'", self.rest())));
+        }
+        Ok(())
+    }
+
+    /// Find every integer in the rest of the text, ignoring anything around them.
+    /// A `-` right before the digits makes the number negative if `T` can be
+    /// negative, unless it follows a letter or digit like in `3-5`. Numbers that
+    /// don't fit in `T` are errors.
+    pub fn ints<T>(&self) -> impl Iterator<Item = Result<T, InputError>> + use<'a, T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let signed = "-1".parse::<T>().is_ok();
+        let mut cursor = *self;
+        std::iter::from_fn(move || {
+            let rest = cursor.rest();
+            let digit = rest.find(|c: char| c.is_ascii_digit())?;
+            let before = &cursor.text[..cursor.pos + digit];
+            let negative = signed
+                && before.ends_with('-')
+                && !before[..before.len() - 1]
+                    .chars()
+                    .next_back()
+                    .is_some_and(char::is_alphanumeric);
+            cursor.pos += if negative { digit - 1 } else { digit };
+            let start = cursor;
+            if negative {
+                cursor.pos += 1;
+            }
+            cursor.take_while(|c| c.is_ascii_digit());
+            Some(cursor.number(start))
+        })
+    }
+
+    /// Find exactly `N` integers in the rest of the text.
+    pub fn ints_n<const N: usize, T>(&self) -> Result<[T; N], InputError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let numbers = self.ints().collect::<Result<Vec<T>, InputError>>()?;
+        let found = numbers.len();
+        numbers
+            .try_into()
+            .map_err(|_| self.error(format!("expected {N} numbers, found {found}")))
+    }
+}
+
+/// Find every integer in the text. See [Cursor::ints].
+pub fn ints<T>(text: &str) -> impl Iterator<Item = Result<T, InputError>> + use<'_, T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    Cursor::new(text).ints()
+}
+
+/// Find every integer on each line of the text, one Vec per line.
+pub fn ints_in_lines<T>(text: &str) -> Result<Vec<Vec<T>>, InputError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    lines(text).map(|line| line.ints().collect()).collect()
+}
+
+/// Find exactly `N` integers in the text.
+pub fn ints_n<const N: usize, T>(text: &str) -> Result<[T; N], InputError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    Cursor::new(text).ints_n()
+}
+
+/// Make a cursor for every line of the text, each knowing its line number.
+pub fn lines(text: &str) -> impl Iterator<Item = Cursor<'_>> {
+    Cursor::new(text).lines()
+}
+
+/// Split the text into sections separated by blank lines. Each section gets a
+/// cursor that knows which line the section starts on.
+pub fn sections(text: &str) -> Vec<Cursor<'_>> {
+    let mut sections = Vec::new();
+    let mut start: Option<(usize, usize)> = None;
+    let mut offset = 0;
+    for (i, line) in text.split_inclusive('\n').enumerate() {
+        if line.trim().is_empty() {
+            if let Some((start_offset, start_line)) = start.take() {
+                sections.push(Cursor::on_line(
+                    text[start_offset..offset].trim_end(),
+                    start_line,
+                ));
+            }
+        } else if start.is_none() {
+            start = Some((offset, i + 1));
+        }
+        offset += line.len();
+    }
+    if let Some((start_offset, start_line)) = start {
+        sections.push(Cursor::on_line(text[start_offset..].trim_end(), start_line));
+    }
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_one_liner() {
+        let mut cursor = Cursor::new("Card  12: 41 48 | 83 86  6");
+        cursor.literal("Card").unwrap();
+        let id: u32 = cursor.unsigned().unwrap();
+        cursor.literal(":").unwrap();
+        let winning: Vec<u8> = cursor.list(|c| c.unsigned()).unwrap();
+        cursor.literal("|").unwrap();
+        let numbers: Vec<u8> = cursor.list(|c| c.unsigned()).unwrap();
+        cursor.end().unwrap();
+        assert_eq!(id, 12);
+        assert_eq!(winning, vec![41, 48]);
+        assert_eq!(numbers, vec![83, 86, 6]);
+    }
+
+    #[test]
+    fn test_separated_by_and_key_value() {
+        let mut cursor = Cursor::new("3 blue, 4 red; 1 red");
+        let draws = cursor
+            .separated_by(",", |c| {
+                c.key_value(|c| c.unsigned::<u32>(), "", |c| c.word())
+            })
+            .unwrap();
+        assert_eq!(draws, vec![(3, "blue"), (4, "red")]);
+        assert_eq!(cursor.rest(), "; 1 red");
+    }
+
+    #[test]
+    fn test_signed() {
+        let mut cursor = Cursor::new("-5 +3 7 -");
+        let numbers: Vec<i32> = cursor.list(|c| c.signed()).unwrap();
+        assert_eq!(numbers, vec![-5, 3, 7]);
+        assert_eq!(cursor.signed::<i32>().unwrap_err().column, 9);
+        assert!(Cursor::new("-3").unsigned::<u32>().is_err());
+    }
+
+    #[test]
+    fn test_error_positions() {
+        let text = "Game 1: ok\nGame 256: big";
+        let mut line = lines(text).nth(1).unwrap();
+        line.literal("Game").unwrap();
+        let error = line.unsigned::<u8>().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+        assert!(
+            error
+                .to_string()
+                .starts_with("line 2, column 6: couldn't read '256'")
+        );
+
+        let mut cursor = Cursor::new("a\nbc d");
+        cursor.word().unwrap();
+        cursor.word().unwrap();
+        let error = cursor.end().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(
+            Cursor::new("x").literal("y").unwrap_err().message,
+            "expected 'y'"
+        );
+    }
+
+    #[test]
+    fn test_quoted() {
+        let mut cursor = Cursor::new(r#" "a \"b\" \\ c", "" "open"#);
+        assert_eq!(cursor.quoted().unwrap(), r#"a "b" \ c"#);
+        cursor.literal(",").unwrap();
+        assert_eq!(cursor.quoted().unwrap(), "");
+        assert_eq!(cursor.quoted().unwrap_err().column, 21);
+        assert!(Cursor::new(r#""\n""#).quoted().is_err());
+    }
+
+    #[test]
+    fn test_ints() {
+        let text = "Card  3: -5 x-7 1-3 --2 +4";
+        let signed: Vec<i32> = ints(text).collect::<Result<_, _>>().unwrap();
+        assert_eq!(signed, vec![3, -5, 7, 1, 3, -2, 4]);
+        let unsigned: Vec<u32> = ints(text).collect::<Result<_, _>>().unwrap();
+        assert_eq!(unsigned, vec![3, 5, 7, 1, 3, 2, 4]);
+        assert_eq!(ints::<u8>("no numbers").count(), 0);
+
+        let error = ints::<u8>("1 2\n 300").last().unwrap().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+
+    #[test]
+    fn test_ints_in_lines_and_n() {
+        assert_eq!(
+            ints_in_lines::<i64>("50 98 2\n\n-1 x 2").unwrap(),
+            vec![vec![50, 98, 2], vec![], vec![-1, 2]]
+        );
+        assert!(ints_in_lines::<u8>("1\n999").is_err());
+
+        let [dest, source, len] = ints_n::<3, i64>("52 50 48").unwrap();
+        assert_eq!((dest, source, len), (52, 50, 48));
+        assert_eq!(
+            ints_n::<3, i64>("1 2").unwrap_err().message,
+            "expected 3 numbers, found 2"
+        );
+    }
+
+    #[test]
+    fn test_sections() {
+        let text = "seeds: 1 2\n\nmap a:\n1 2 3\n\n\nmap b:\n4 5 6\n";
+        let sections = sections(text);
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[1].rest(), "map a:\n1 2 3");
+        assert_eq!(sections[2].rest(), "map b:\n4 5 6");
+        assert_eq!(
+            sections[2]
+                .lines()
+                .map(|l| l.error("").line)
+                .collect::<Vec<usize>>(),
+            vec![7, 8]
+        );
+        let mut last = sections[2];
+        last.literal("map b:").unwrap();
+        last.unsigned::<u8>().unwrap();
+        assert_eq!(last.literal("x").unwrap_err().line, 8);
+    }
+}
@@ -0,0 +1,109 @@
+//! Area and lattice point counts for polygons with whole number corners, like
+//! the loop in a pipe maze or a dug out lagoon. The corners are given in order
+//! around the polygon, either way round, and the last one joins back to the
+//! first.
+
+use crate::coord::{Coord, Signed, abs};
+
+/// Get twice the area of the polygon with the shoelace formula. Twice the area
+/// is always a whole number.
+pub fn double_area<T: Signed>(corners: &[Coord<T>]) -> T {
+    let sum = edges(corners).fold(T::from(0), |sum, (a, b)| sum + a.x * b.y - b.x * a.y);
+    abs(sum)
+}
+
+/// Get the number of whole number coordinates on the edges of the polygon. For
+/// edges that only go straight across or down this is the length of the
+/// perimeter.
+pub fn perimeter<T: Signed>(corners: &[Coord<T>]) -> T {
+    edges(corners).fold(T::from(0), |sum, (a, b)| {
+        let diff = b - a;
+        sum + gcd(abs(diff.x), abs(diff.y))
+    })
+}
+
+/// Get the number of whole number coordinates strictly inside the polygon with
+/// Pick's theorem.
+pub fn interior_points<T: Signed>(corners: &[Coord<T>]) -> T {
+    let two = T::from(2);
+    (double_area(corners) - perimeter(corners) + two) / two
+}
+
+/// Get the number of whole number coordinates inside or on the edges of the
+/// polygon.
+pub fn lattice_points<T: Signed>(corners: &[Coord<T>]) -> T {
+    interior_points(corners) + perimeter(corners)
+}
+
+/// Iterate the edges of the polygon as pairs of corners.
+fn edges<T: Signed>(corners: &[Coord<T>]) -> impl Iterator<Item = (Coord<T>, Coord<T>)> + '_ {
+    corners
+        .iter()
+        .zip(corners.iter().cycle().skip(1))
+        .map(|(a, b)| (*a, *b))
+}
+
+/// Greatest common divisor of two numbers that aren't negative.
+fn gcd<T: Signed>(mut a: T, mut b: T) -> T {
+    while b != T::from(0) {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rectangle() {
+        let corners = [
+            Coord::new(0, 0),
+            Coord::new(6, 0),
+            Coord::new(6, 5),
+            Coord::new(0, 5),
+        ];
+        assert_eq!(double_area(&corners), 60);
+        assert_eq!(perimeter(&corners), 22);
+        assert_eq!(interior_points(&corners), 20);
+        assert_eq!(lattice_points(&corners), 42);
+        let mut reversed = corners;
+        reversed.reverse();
+        assert_eq!(double_area(&reversed), 60);
+    }
+
+    #[test]
+    fn test_lagoon() {
+        // Trench from the day 18 example, which holds 62 cubic meters.
+        let corners: Vec<Coord<i64>> = [
+            (0, 0),
+            (6, 0),
+            (6, 5),
+            (4, 5),
+            (4, 7),
+            (6, 7),
+            (6, 9),
+            (1, 9),
+            (1, 7),
+            (0, 7),
+            (0, 5),
+            (2, 5),
+            (2, 2),
+            (0, 2),
+        ]
+        .into_iter()
+        .map(|(x, y)| Coord::new(x, y))
+        .collect();
+        assert_eq!(perimeter(&corners), 38);
+        assert_eq!(lattice_points(&corners), 62);
+    }
+
+    #[test]
+    fn test_diagonal_edges() {
+        let triangle = [Coord::new(0, 0), Coord::new(4, 0), Coord::new(0, 4)];
+        assert_eq!(double_area(&triangle), 16);
+        assert_eq!(perimeter(&triangle), 12);
+        assert_eq!(interior_points(&triangle), 3);
+        assert_eq!(double_area::<i32>(&[]), 0);
+    }
+}
@@ -0,0 +1,140 @@
+//! Module for rectangles of coordinates, like the bounds of a grid or the box
+//! around a set of points.
+
+use std::fmt;
+
+use crate::coord::{Coord, Signed};
+
+/// Rectangle of coordinates including both corners, so it's never empty.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Rect<T = i32> {
+    /// Corner with the smallest x and y, the top left.
+    pub min: Coord<T>,
+    /// Corner with the largest x and y, the bottom right.
+    pub max: Coord<T>,
+}
+
+impl<T: Signed> Rect<T> {
+    /// Make a new Rect with any two opposite corners.
+    pub fn new(a: Coord<T>, b: Coord<T>) -> Self {
+        Self {
+            min: Coord::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Coord::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    /// Get the smallest Rect holding all the coordinates, or None if there are
+    /// none.
+    pub fn bounding(coords: &[Coord<T>]) -> Option<Self> {
+        let (first, rest) = coords.split_first()?;
+        Some(rest.iter().fold(Self::new(*first, *first), |rect, coord| {
+            rect.union(&Self::new(*coord, *coord))
+        }))
+    }
+
+    /// Number of columns.
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::from(1)
+    }
+
+    /// Number of rows.
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::from(1)
+    }
+
+    /// Rect includes the coordinate.
+    pub fn contains(&self, coord: &Coord<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&coord.x) && (self.min.y..=self.max.y).contains(&coord.y)
+    }
+
+    /// Get the part covered by both rects, or None if they don't overlap.
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let min = Coord::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y));
+        let max = Coord::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y));
+        (min.x <= max.x && min.y <= max.y).then_some(Self { min, max })
+    }
+
+    /// Get the smallest Rect covering both rects.
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            min: Coord::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            max: Coord::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        }
+    }
+
+    /// Iterate all coordinates in row-major order.
+    pub fn coords(&self) -> impl Iterator<Item = Coord<T>> + use<T> {
+        let (min, max) = (self.min, self.max);
+        let mut next = Some(min);
+        std::iter::from_fn(move || {
+            let current = next?;
+            next = if current.x < max.x {
+                Some(Coord::new(current.x + T::from(1), current.y))
+            } else if current.y < max.y {
+                Some(Coord::new(min.x, current.y + T::from(1)))
+            } else {
+                None
+            };
+            Some(current)
+        })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Rect<T> {
+    /// Format the rect as its two corners.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} to {}", self.min, self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_and_size() {
+        let rect = Rect::new(Coord::new(3, -1), Coord::new(-2, 4));
+        assert_eq!(rect.min, Coord::new(-2, -1));
+        assert_eq!(rect.max, Coord::new(3, 4));
+        assert_eq!(rect.width(), 6);
+        assert_eq!(rect.height(), 6);
+        assert!(rect.contains(&Coord::new(-2, 4)));
+        assert!(!rect.contains(&Coord::new(4, 0)));
+        assert_eq!(rect.to_string(), "(-2, -1) to (3, 4)");
+    }
+
+    #[test]
+    fn test_intersect_and_union() {
+        let a = Rect::new(Coord::new(0, 0), Coord::new(4, 4));
+        let b = Rect::new(Coord::new(3, -2), Coord::new(6, 1));
+        assert_eq!(
+            a.intersect(&b),
+            Some(Rect::new(Coord::new(3, 0), Coord::new(4, 1)))
+        );
+        assert_eq!(a.union(&b), Rect::new(Coord::new(0, -2), Coord::new(6, 4)));
+        let apart = Rect::new(Coord::new(5, 5), Coord::new(6, 6));
+        assert_eq!(a.intersect(&apart), None);
+        // Touching corners share a coordinate.
+        let corner = Rect::new(Coord::new(4, 4), Coord::new(5, 5));
+        assert_eq!(a.intersect(&corner).map(|r| r.width()), Some(1));
+    }
+
+    #[test]
+    fn test_bounding_and_coords() {
+        assert_eq!(Rect::<i32>::bounding(&[]), None);
+        let rect =
+            Rect::bounding(&[Coord::new(0, -1), Coord::new(-1, 0), Coord::new(0, 0)]).unwrap();
+        assert_eq!(rect, Rect::new(Coord::new(-1, -1), Coord::new(0, 0)));
+        assert_eq!(
+            rect.coords().collect::<Vec<Coord>>(),
+            vec![
+                Coord::new(-1, -1),
+                Coord::new(0, -1),
+                Coord::new(-1, 0),
+                Coord::new(0, 0)
+            ]
+        );
+        let wide: Rect<i64> = Rect::new(Coord::new(1 << 40, 0), Coord::new((1 << 40) + 2, 0));
+        assert_eq!(wide.coords().count(), 3);
+    }
+}
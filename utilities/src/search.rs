@@ -0,0 +1,528 @@
+//! Graph searches (BFS, Dijkstra and A*). A lot of AoC puzzles are just finding
+//! the shortest way through a map, so these work on any state that can be hashed.
+//! Plain grid searches use [Coord] as the state, puzzles with extra rules (like
+//! limits on how far you can go straight) can put direction and step counts in
+//! the state too.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+use crate::coord::Coord;
+use crate::direction::Direction;
+use crate::grid::Grid;
+use crate::tiled::TiledGrid;
+
+/// Which coordinates count as neighbours when searching a grid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Neighbors {
+    /// North, east, south and west.
+    Four,
+    /// All eight surrounding coordinates.
+    Eight,
+    /// Offsets to add to the current coordinate.
+    Custom(Vec<Coord>),
+}
+
+impl Neighbors {
+    /// Offsets for the four straight directions.
+    const FOUR: [Coord; 4] = [
+        Direction::N.offset(),
+        Direction::E.offset(),
+        Direction::S.offset(),
+        Direction::W.offset(),
+    ];
+
+    /// Offsets for all eight directions.
+    const EIGHT: [Coord; 8] = [
+        Direction::N.offset(),
+        Direction::NE.offset(),
+        Direction::E.offset(),
+        Direction::SE.offset(),
+        Direction::S.offset(),
+        Direction::SW.offset(),
+        Direction::W.offset(),
+        Direction::NW.offset(),
+    ];
+
+    /// Offsets used by this policy.
+    fn offsets(&self) -> &[Coord] {
+        match self {
+            Neighbors::Four => &Self::FOUR,
+            Neighbors::Eight => &Self::EIGHT,
+            Neighbors::Custom(offsets) => offsets,
+        }
+    }
+
+    /// Iterate the neighbours of the coordinate.
+    pub fn of(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        self.offsets().iter().map(move |offset| coord + *offset)
+    }
+}
+
+/// Result of a search. Holds the cost to reach every state that was found and
+/// enough information to rebuild the path to any of them.
+#[derive(Clone, Debug)]
+pub struct SearchResult<S> {
+    /// Lowest cost found to reach each state. For BFS this is the step count.
+    pub distances: HashMap<S, u64>,
+    /// State each state was reached from. Start states have no parent.
+    parents: HashMap<S, S>,
+    /// First goal state reached, if the search had a goal and found it.
+    pub goal: Option<S>,
+}
+
+impl<S: Clone + Eq + Hash> SearchResult<S> {
+    /// Make a new empty result.
+    fn new() -> Self {
+        Self {
+            distances: HashMap::new(),
+            parents: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    /// Cost to reach the state, if it was reached.
+    pub fn distance(&self, state: &S) -> Option<u64> {
+        self.distances.get(state).copied()
+    }
+
+    /// Cost to reach the goal, if one was found.
+    pub fn goal_distance(&self) -> Option<u64> {
+        self.goal.as_ref().and_then(|goal| self.distance(goal))
+    }
+
+    /// Path from a start state to the state, including both ends.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        if !self.distances.contains_key(state) {
+            return None;
+        }
+        let mut path = vec![state.clone()];
+        let mut current = state;
+        while let Some(parent) = self.parents.get(current) {
+            path.push(parent.clone());
+            current = parent;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Path from a start state to the goal, if one was found.
+    pub fn goal_path(&self) -> Option<Vec<S>> {
+        self.goal.as_ref().and_then(|goal| self.path_to(goal))
+    }
+}
+
+/// Breadth first search where every move costs 1. Stops at the first state that
+/// `is_goal` accepts, use `|_| false` to explore everything reachable.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut result = SearchResult::new();
+    let mut queue: VecDeque<S> = VecDeque::new();
+    for start in starts {
+        if !result.distances.contains_key(&start) {
+            result.distances.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            result.goal = Some(state);
+            break;
+        }
+        let distance = result.distances[&state];
+        for next in successors(&state) {
+            if result.distances.contains_key(&next) {
+                continue;
+            }
+            result.distances.insert(next.clone(), distance + 1);
+            result.parents.insert(next.clone(), state.clone());
+            queue.push_back(next);
+        }
+    }
+    result
+}
+
+/// Dijkstra's shortest path where `successors` gives each next state and the
+/// cost of moving to it. Stops at the first state that `is_goal` accepts.
+pub fn dijkstra<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    astar(starts, successors, |_| 0, is_goal)
+}
+
+/// A* search. `heuristic` estimates the remaining cost to a goal and must never
+/// overestimate it or the result might not be the shortest path.
+pub fn astar<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> u64,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let mut result = SearchResult::new();
+    // The heap holds (estimate, cost, index into states) so S doesn't need Ord.
+    let mut heap: BinaryHeap<Reverse<(u64, u64, usize)>> = BinaryHeap::new();
+    let mut states: Vec<S> = Vec::new();
+
+    for start in starts {
+        if !result.distances.contains_key(&start) {
+            result.distances.insert(start.clone(), 0);
+            heap.push(Reverse((heuristic(&start), 0, states.len())));
+            states.push(start);
+        }
+    }
+
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        let state = states[index].clone();
+        // Skip stale heap entries for states we found a cheaper way to.
+        if result.distances[&state] < cost {
+            continue;
+        }
+        if is_goal(&state) {
+            result.goal = Some(state);
+            break;
+        }
+        for (next, step_cost) in successors(&state) {
+            let next_cost = cost + step_cost;
+            if result
+                .distances
+                .get(&next)
+                .is_some_and(|known| *known <= next_cost)
+            {
+                continue;
+            }
+            result.distances.insert(next.clone(), next_cost);
+            result.parents.insert(next.clone(), state.clone());
+            heap.push(Reverse((
+                next_cost + heuristic(&next),
+                next_cost,
+                states.len(),
+            )));
+            states.push(next);
+        }
+    }
+    result
+}
+
+/// Breadth first search over grid coordinates, with `cell` looking up what's
+/// at a coordinate. Shared by the grid and the tiled view.
+fn grid_bfs<'a, T: 'a>(
+    start: Coord,
+    neighbors: &Neighbors,
+    cell: impl Fn(&Coord) -> Option<&'a T>,
+    mut passable: impl FnMut(Coord, Coord, &T) -> bool,
+    is_goal: impl FnMut(&Coord) -> bool,
+) -> SearchResult<Coord> {
+    bfs(
+        [start],
+        |from: &Coord| {
+            let from = *from;
+            neighbors
+                .of(from)
+                .filter(|to| cell(to).is_some_and(|cell| passable(from, *to, cell)))
+                .collect::<Vec<Coord>>()
+        },
+        is_goal,
+    )
+}
+
+/// A* search over grid coordinates, with `cell` looking up what's at a
+/// coordinate. Shared by the grid and the tiled view.
+fn grid_astar<'a, T: 'a>(
+    start: Coord,
+    neighbors: &Neighbors,
+    cell: impl Fn(&Coord) -> Option<&'a T>,
+    mut cost: impl FnMut(Coord, Coord, &T) -> Option<u64>,
+    heuristic: impl FnMut(&Coord) -> u64,
+    is_goal: impl FnMut(&Coord) -> bool,
+) -> SearchResult<Coord> {
+    astar(
+        [start],
+        |from: &Coord| {
+            let from = *from;
+            neighbors
+                .of(from)
+                .filter_map(|to| cost(from, to, cell(&to)?).map(|c| (to, c)))
+                .collect::<Vec<(Coord, u64)>>()
+        },
+        heuristic,
+        is_goal,
+    )
+}
+
+impl<T> Grid<T> {
+    /// Breadth first search from `start` over cells that `passable` allows
+    /// moving onto. `passable` gets the coordinate moved from, the coordinate
+    /// moved to and the cell there.
+    pub fn bfs(
+        &self,
+        start: Coord,
+        neighbors: &Neighbors,
+        passable: impl FnMut(Coord, Coord, &T) -> bool,
+        is_goal: impl FnMut(&Coord) -> bool,
+    ) -> SearchResult<Coord> {
+        grid_bfs(start, neighbors, |coord| self.get(coord), passable, is_goal)
+    }
+
+    /// Dijkstra's shortest path from `start`. `cost` gets the coordinate moved
+    /// from, the coordinate moved to and the cell there, and returns None if the
+    /// move isn't allowed.
+    pub fn dijkstra(
+        &self,
+        start: Coord,
+        neighbors: &Neighbors,
+        cost: impl FnMut(Coord, Coord, &T) -> Option<u64>,
+        is_goal: impl FnMut(&Coord) -> bool,
+    ) -> SearchResult<Coord> {
+        self.astar(start, neighbors, cost, |_| 0, is_goal)
+    }
+
+    /// A* search from `start`. Same as [Grid::dijkstra] plus a `heuristic` that
+    /// must never overestimate the remaining cost.
+    pub fn astar(
+        &self,
+        start: Coord,
+        neighbors: &Neighbors,
+        cost: impl FnMut(Coord, Coord, &T) -> Option<u64>,
+        heuristic: impl FnMut(&Coord) -> u64,
+        is_goal: impl FnMut(&Coord) -> bool,
+    ) -> SearchResult<Coord> {
+        let cell = |coord: &Coord| self.get(coord);
+        grid_astar(start, neighbors, cell, cost, heuristic, is_goal)
+    }
+}
+
+impl<T> TiledGrid<'_, T> {
+    /// Breadth first search from `start` like [Grid::bfs], wrapping around the
+    /// edges. The view never ends, so `passable` or `is_goal` has to stop the
+    /// search.
+    pub fn bfs(
+        &self,
+        start: Coord,
+        neighbors: &Neighbors,
+        passable: impl FnMut(Coord, Coord, &T) -> bool,
+        is_goal: impl FnMut(&Coord) -> bool,
+    ) -> SearchResult<Coord> {
+        grid_bfs(start, neighbors, |coord| self.get(coord), passable, is_goal)
+    }
+
+    /// Dijkstra's shortest path from `start` like [Grid::dijkstra], wrapping
+    /// around the edges.
+    pub fn dijkstra(
+        &self,
+        start: Coord,
+        neighbors: &Neighbors,
+        cost: impl FnMut(Coord, Coord, &T) -> Option<u64>,
+        is_goal: impl FnMut(&Coord) -> bool,
+    ) -> SearchResult<Coord> {
+        self.astar(start, neighbors, cost, |_| 0, is_goal)
+    }
+
+    /// A* search from `start` like [Grid::astar], wrapping around the edges.
+    pub fn astar(
+        &self,
+        start: Coord,
+        neighbors: &Neighbors,
+        cost: impl FnMut(Coord, Coord, &T) -> Option<u64>,
+        heuristic: impl FnMut(&Coord) -> u64,
+        is_goal: impl FnMut(&Coord) -> bool,
+    ) -> SearchResult<Coord> {
+        let cell = |coord: &Coord| self.get(coord);
+        grid_astar(start, neighbors, cell, cost, heuristic, is_goal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E";
+
+    fn find(grid: &Grid, target: char) -> Coord {
+        grid.iter()
+            .find(|(_, c)| **c == target)
+            .map(|(coord, _)| coord)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let origin = Coord::new(0, 0);
+        assert_eq!(Neighbors::Four.of(origin).count(), 4);
+        assert_eq!(Neighbors::Eight.of(origin).count(), 8);
+        let knight = Neighbors::Custom(vec![Coord::new(1, 2), Coord::new(2, 1)]);
+        assert_eq!(
+            knight.of(origin).collect::<Vec<Coord>>(),
+            vec![Coord::new(1, 2), Coord::new(2, 1)]
+        );
+    }
+
+    #[test]
+    fn test_grid_bfs() {
+        let grid = Grid::new_from_string(MAZE);
+        let start = find(&grid, 'S');
+        let end = find(&grid, 'E');
+        let result = grid.bfs(start, &Neighbors::Four, |_, _, c| *c != '#', |c| *c == end);
+        assert_eq!(result.goal, Some(end));
+        assert_eq!(result.goal_distance(), Some(15));
+        let path = result.goal_path().unwrap();
+        assert_eq!(path.len(), 16);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&end));
+        assert!(path.iter().all(|c| grid[*c] != '#'));
+    }
+
+    #[test]
+    fn test_grid_bfs_eight_way_full_map() {
+        let grid = Grid::new_from_string(MAZE);
+        let start = find(&grid, 'S');
+        let result = grid.bfs(start, &Neighbors::Eight, |_, _, c| *c != '#', |_| false);
+        assert_eq!(result.goal, None);
+        assert_eq!(result.distance(&Coord::new(2, 2)), Some(3));
+        assert_eq!(result.distance(&Coord::new(3, 0)), None);
+    }
+
+    #[test]
+    fn test_grid_dijkstra_and_astar() {
+        let grid = Grid::parse_with("1163\n1381\n2136", |c| c.to_digit(10).unwrap() as u64);
+        let end = Coord::new(3, 2);
+        let cost = |_, _, c: &u64| Some(*c);
+        let dijkstra = grid.dijkstra(Coord::new(0, 0), &Neighbors::Four, cost, |c| *c == end);
+        assert_eq!(dijkstra.goal_distance(), Some(13));
+        let astar = grid.astar(
+            Coord::new(0, 0),
+            &Neighbors::Four,
+            cost,
+            |c| ((end.x - c.x).abs() + (end.y - c.y).abs()) as u64,
+            |c| *c == end,
+        );
+        assert_eq!(astar.goal_distance(), Some(13));
+        let path = astar.goal_path().unwrap();
+        let total: u64 = path.iter().skip(1).map(|c| grid[*c]).sum();
+        assert_eq!(total, 13);
+    }
+
+    #[test]
+    fn test_state_search_limits_straight_moves() {
+        // Cheapest path is straight along the top row, but only 2 moves in a
+        // row are allowed in the same direction.
+        let grid = Grid::parse_with("11111\n99999", |c| c.to_digit(10).unwrap() as u64);
+        let end = Coord::new(4, 0);
+        let start = (Coord::new(0, 0), Coord::new(0, 0), 0_u8);
+        let result = dijkstra(
+            [start],
+            |(coord, dir, steps): &(Coord, Coord, u8)| {
+                let mut next = Vec::new();
+                for offset in Neighbors::Four.offsets() {
+                    // No reversing.
+                    if *offset + *dir == Coord::new(0, 0) && *dir != Coord::new(0, 0) {
+                        continue;
+                    }
+                    let run = if offset == dir { steps + 1 } else { 1 };
+                    if run > 2 {
+                        continue;
+                    }
+                    let to = *coord + *offset;
+                    if let Some(cost) = grid.get(&to) {
+                        next.push(((to, *offset, run), *cost));
+                    }
+                }
+                next
+            },
+            |(coord, _, _)| *coord == end,
+        );
+        let unlimited = grid.dijkstra(
+            Coord::new(0, 0),
+            &Neighbors::Four,
+            |_, _, c| Some(*c),
+            |c| *c == end,
+        );
+        assert_eq!(unlimited.goal_distance(), Some(4));
+        assert_eq!(result.goal_distance(), Some(22));
+    }
+
+    #[test]
+    fn test_tiled_search() {
+        // Garden from the day 21 example.
+        let garden = Grid::new_from_string(
+            "\
+...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........",
+        );
+        let start = Coord::new(5, 5);
+        // Plots that can be stood on after exactly `steps` steps: the ones at
+        // most that far away with the same parity. Nothing further than
+        // `steps` in a straight line can be that close, so limit the search
+        // to that.
+        let reach = |steps: u64| {
+            garden
+                .tiled()
+                .bfs(
+                    start,
+                    &Neighbors::Four,
+                    |_, to, cell| *cell != '#' && to.manhattan(&start) as u64 <= steps,
+                    |_| false,
+                )
+                .distances
+                .values()
+                .filter(|distance| **distance <= steps && **distance % 2 == steps % 2)
+                .count()
+        };
+        assert_eq!(reach(6), 16);
+        assert_eq!(reach(10), 50);
+        assert_eq!(reach(50), 1594);
+        assert_eq!(reach(100), 6536);
+
+        let garden = Grid::new_from_string("...\n.#.\n...");
+        let start = Coord::new(1, 0);
+        let tiled = garden.tiled();
+        let result = tiled.dijkstra(
+            start,
+            &Neighbors::Four,
+            |_, _, cell| (*cell != '#').then_some(1),
+            |coord| tiled.tile(coord) == Coord::new(0, -2),
+        );
+        // Straight up hits the wall in the middle of the tile above, so step
+        // around it.
+        assert_eq!(result.goal_distance(), Some(5));
+        let path = result.goal_path().unwrap();
+        assert_eq!(
+            path.last().map(|coord| tiled.tile(coord)),
+            Some(Coord::new(0, -2))
+        );
+    }
+}
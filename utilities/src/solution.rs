@@ -0,0 +1,261 @@
+//! Common shape for every day's solution so one runner can solve any of them.
+//! Each day parses its input once and both parts work off the parsed input.
+
+use std::{
+    error::Error,
+    fmt,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    answers::{Ledger, Verdict},
+    input::YEAR,
+};
+
+/// Error returned when a day can't parse its input.
+pub type ParseError = Box<dyn Error>;
+
+/// Which part of the puzzle to solve.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// Both parts in order.
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    /// Get the part from its number, 1 or 2.
+    pub fn from_number(number: u8) -> Option<Self> {
+        match number {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    /// Format the part as its number.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A day's puzzle solution.
+pub trait Solution {
+    /// Puzzle input after parsing, shared by both parts.
+    type Input;
+    /// Type of the answer for both parts.
+    type Answer: fmt::Display;
+
+    /// Title of the puzzle.
+    const TITLE: &'static str;
+
+    /// Parse the puzzle input text.
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    /// Solve part 1.
+    fn part1(input: &Self::Input) -> Self::Answer;
+
+    /// Solve part 2.
+    fn part2(input: &Self::Input) -> Self::Answer;
+
+    /// Solve one part of already parsed input.
+    fn solve(input: &Self::Input, part: Part) -> Self::Answer {
+        match part {
+            Part::One => Self::part1(input),
+            Part::Two => Self::part2(input),
+        }
+    }
+
+    /// Read, parse and solve one part from a file. Panics if anything goes wrong,
+    /// mostly useful for tests.
+    fn solve_file(file_name: &str, part: Part) -> Self::Answer {
+        let file_contents = std::fs::read_to_string(file_name).expect("Couldn't open file");
+        let input = Self::parse(&file_contents).unwrap_or_else(|e| panic!("{e}"));
+        Self::solve(&input, part)
+    }
+
+    /// Load, parse and solve one part of a day's real input from the input
+    /// cache. Panics if anything goes wrong, mostly useful for tests.
+    fn solve_input(day: u8, part: Part) -> Self::Answer {
+        let contents = crate::input::load(day).unwrap_or_else(|e| panic!("{e}"));
+        let input = Self::parse(&contents).unwrap_or_else(|e| panic!("{e}"));
+        Self::solve(&input, part)
+    }
+
+    /// Solve one part's example and check the answer against the answers
+    /// ledger. The example file is relative to the day's directory, where tests
+    /// run. Panics if the ledger has no example or the answer is different.
+    fn check_example(day: u8, part: Part) {
+        let path = Ledger::default_path();
+        let ledger = Ledger::load(&path).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+        let example = ledger
+            .get(YEAR, day, part)
+            .and_then(|answers| answers.example.as_ref())
+            .unwrap_or_else(|| panic!("no example for day {day} part {part} in the ledger"));
+        assert_eq!(
+            Self::solve_file(&example.file, part).to_string(),
+            example.answer,
+            "day {day} part {part} example {}",
+            example.file
+        );
+    }
+
+    /// Solve one part of a day's real input and check the answer against the
+    /// answers ledger. Panics unless the ledger says it's correct.
+    fn check_input(day: u8, part: Part) {
+        let answer = Self::solve_input(day, part).to_string();
+        let path = Ledger::default_path();
+        let ledger = Ledger::load(&path).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+        let verdict = ledger.check(YEAR, day, part, &answer);
+        assert_eq!(
+            verdict,
+            Verdict::Correct,
+            "day {day} part {part} answer {answer} is {verdict}"
+        );
+    }
+}
+
+/// Answer to one part and how long it took to solve.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartResult {
+    /// Part that was solved.
+    pub part: Part,
+    /// Answer formatted as a string.
+    pub answer: String,
+    /// Time spent solving, not counting parsing or formatting the answer.
+    pub time: Duration,
+}
+
+/// Results of running a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayResult {
+    /// Time spent parsing the input.
+    pub parse_time: Duration,
+    /// Results in the same order the parts were asked for.
+    pub parts: Vec<PartResult>,
+}
+
+/// Parse the input once then solve each of the parts, timing each step.
+fn run<S: Solution>(input: &str, parts: &[Part]) -> Result<DayResult, ParseError> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parse_time = start.elapsed();
+    let parts = parts
+        .iter()
+        .map(|part| {
+            let start = Instant::now();
+            let answer = S::solve(&input, *part);
+            let time = start.elapsed();
+            PartResult {
+                part: *part,
+                answer: answer.to_string(),
+                time,
+            }
+        })
+        .collect();
+    Ok(DayResult { parse_time, parts })
+}
+
+/// A registered day. Hides the day's input and answer types so days can be kept
+/// in a single list.
+#[derive(Clone, Copy)]
+pub struct Day {
+    /// Day of the month the puzzle is for.
+    pub number: u8,
+    /// Title of the puzzle.
+    pub title: &'static str,
+    /// Solves the parts from the raw input.
+    run: fn(&str, &[Part]) -> Result<DayResult, ParseError>,
+}
+
+impl Day {
+    /// Register a solution as a day.
+    pub const fn new<S: Solution>(number: u8) -> Self {
+        Self {
+            number,
+            title: S::TITLE,
+            run: run::<S>,
+        }
+    }
+
+    /// Solve the parts from the raw input. Results are in the same order as
+    /// `parts`.
+    pub fn run(&self, input: &str, parts: &[Part]) -> Result<DayResult, ParseError> {
+        (self.run)(input, parts)
+    }
+}
+
+impl fmt::Debug for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Day")
+            .field("number", &self.number)
+            .field("title", &self.title)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sums numbers for part 1, multiplies them for part 2.
+    struct Numbers;
+
+    impl Solution for Numbers {
+        type Input = Vec<u32>;
+        type Answer = u32;
+
+        const TITLE: &'static str = "Numbers";
+
+        fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+            Ok(input
+                .split_whitespace()
+                .map(|n| n.parse())
+                .collect::<Result<Vec<u32>, _>>()?)
+        }
+
+        fn part1(input: &Vec<u32>) -> u32 {
+            input.iter().sum()
+        }
+
+        fn part2(input: &Vec<u32>) -> u32 {
+            input.iter().product()
+        }
+    }
+
+    #[test]
+    fn test_part_from_number() {
+        assert_eq!(Part::from_number(1), Some(Part::One));
+        assert_eq!(Part::from_number(2), Some(Part::Two));
+        assert_eq!(Part::from_number(3), None);
+    }
+
+    #[test]
+    fn test_day_run() {
+        let day = Day::new::<Numbers>(1);
+        assert_eq!(day.title, "Numbers");
+        let answers = |result: DayResult| -> Vec<(Part, String)> {
+            result
+                .parts
+                .into_iter()
+                .map(|part| (part.part, part.answer))
+                .collect()
+        };
+        assert_eq!(
+            answers(day.run("2 3 4", &Part::BOTH).unwrap()),
+            vec![(Part::One, "9".to_string()), (Part::Two, "24".to_string())]
+        );
+        assert_eq!(
+            answers(day.run("2 3 4", &[Part::Two]).unwrap()),
+            vec![(Part::Two, "24".to_string())]
+        );
+        assert!(day.run("2 x 4", &[Part::One]).is_err());
+    }
+}
@@ -0,0 +1,269 @@
+//! Finding many patterns in text at once with an Aho-Corasick automaton: a trie
+//! of the patterns where every node also links to the longest proper suffix of
+//! itself that's in the trie, so one pass over the text finds every match,
+//! overlapping ones included.
+
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, VecDeque},
+    ops::Range,
+};
+
+/// A pattern found in the text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Match {
+    /// Index of the pattern in the list the matcher was made from.
+    pub pattern: usize,
+    /// Byte offset of the start of the match.
+    pub start: usize,
+    /// Byte offset just past the end of the match.
+    pub end: usize,
+}
+
+impl Match {
+    /// Get the bytes of the text that matched.
+    pub fn span(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+/// Node of the trie.
+#[derive(Clone, Debug, Default)]
+struct Node {
+    children: HashMap<u8, usize>,
+    /// Node for the longest proper suffix of this one that's also in the trie.
+    fail: usize,
+    /// Patterns ending at this node, including through the failure links.
+    outputs: Vec<usize>,
+}
+
+/// Trie with failure links over the bytes of the patterns.
+#[derive(Clone, Debug)]
+struct Automaton {
+    nodes: Vec<Node>,
+}
+
+impl Automaton {
+    /// Make the automaton for the patterns, given as bytes.
+    fn new<'a>(patterns: impl Iterator<Item = &'a [u8]>) -> Self {
+        let mut nodes = vec![Node::default()];
+        for (pattern, bytes) in patterns.enumerate() {
+            let mut node = 0;
+            for byte in bytes {
+                node = match nodes[node].children.get(byte) {
+                    Some(child) => *child,
+                    None => {
+                        nodes.push(Node::default());
+                        let child = nodes.len() - 1;
+                        nodes[node].children.insert(*byte, child);
+                        child
+                    }
+                };
+            }
+            nodes[node].outputs.push(pattern);
+        }
+
+        // Breadth first so every node's failure link is done before its children.
+        let mut queue: VecDeque<usize> = nodes[0].children.values().copied().collect();
+        while let Some(node) = queue.pop_front() {
+            let children: Vec<(u8, usize)> = nodes[node]
+                .children
+                .iter()
+                .map(|(byte, child)| (*byte, *child))
+                .collect();
+            for (byte, child) in children {
+                let mut fail = nodes[node].fail;
+                let fail = loop {
+                    if let Some(next) = nodes[fail].children.get(&byte) {
+                        break *next;
+                    }
+                    if fail == 0 {
+                        break 0;
+                    }
+                    fail = nodes[fail].fail;
+                };
+                nodes[child].fail = fail;
+                let inherited = nodes[fail].outputs.clone();
+                nodes[child].outputs.extend(inherited);
+                queue.push_back(child);
+            }
+        }
+        Self { nodes }
+    }
+
+    /// Move from `node` on `byte`.
+    fn step(&self, mut node: usize, byte: u8) -> usize {
+        loop {
+            if let Some(next) = self.nodes[node].children.get(&byte) {
+                return *next;
+            }
+            if node == 0 {
+                return 0;
+            }
+            node = self.nodes[node].fail;
+        }
+    }
+}
+
+/// Finds every one of a set of patterns in text in a single pass.
+#[derive(Clone, Debug)]
+pub struct Matcher {
+    forward: Automaton,
+    /// Automaton of the reversed patterns for searching from the end.
+    reverse: Automaton,
+    /// Length of each pattern in bytes.
+    lengths: Vec<usize>,
+}
+
+impl Matcher {
+    /// Make a matcher for the patterns. Panics if a pattern is empty.
+    pub fn new<P: AsRef<str>>(patterns: impl IntoIterator<Item = P>) -> Self {
+        let patterns: Vec<P> = patterns.into_iter().collect();
+        assert!(
+            patterns.iter().all(|p| !p.as_ref().is_empty()),
+            "Patterns can't be empty."
+        );
+        let reversed: Vec<Vec<u8>> = patterns
+            .iter()
+            .map(|p| p.as_ref().bytes().rev().collect())
+            .collect();
+        Self {
+            forward: Automaton::new(patterns.iter().map(|p| p.as_ref().as_bytes())),
+            reverse: Automaton::new(reversed.iter().map(Vec::as_slice)),
+            lengths: patterns.iter().map(|p| p.as_ref().len()).collect(),
+        }
+    }
+
+    /// Get how many patterns there are.
+    pub fn len(&self) -> usize {
+        self.lengths.len()
+    }
+
+    /// No patterns at all, so nothing ever matches.
+    pub fn is_empty(&self) -> bool {
+        self.lengths.is_empty()
+    }
+
+    /// Find every match, overlapping ones included, in order of where they end.
+    /// Matches ending at the same place are in the order the patterns were given.
+    pub fn find_iter<'a>(&'a self, text: &'a str) -> impl Iterator<Item = Match> + 'a {
+        let mut node = 0;
+        text.bytes().enumerate().flat_map(move |(i, byte)| {
+            node = self.forward.step(node, byte);
+            let mut outputs = self.forward.nodes[node].outputs.clone();
+            outputs.sort_unstable();
+            outputs.into_iter().map(move |pattern| Match {
+                pattern,
+                start: i + 1 - self.lengths[pattern],
+                end: i + 1,
+            })
+        })
+    }
+
+    /// Find the match that starts first. The longest wins if several start at
+    /// the same place.
+    pub fn find_first(&self, text: &str) -> Option<Match> {
+        let longest = self.lengths.iter().copied().max()?;
+        let mut best: Option<Match> = None;
+        for found in self.find_iter(text) {
+            if best.is_some_and(|best| found.end > best.start + longest) {
+                // Nothing ending this late can start before the best match.
+                break;
+            }
+            let better = best.is_none_or(|best| {
+                (found.start, Reverse(found.end)) < (best.start, Reverse(best.end))
+            });
+            if better {
+                best = Some(found);
+            }
+        }
+        best
+    }
+
+    /// Find the match that starts last by searching backwards from the end of
+    /// the text. The longest wins if several start at the same place.
+    pub fn find_last(&self, text: &str) -> Option<Match> {
+        let mut node = 0;
+        for (i, byte) in text.bytes().enumerate().rev() {
+            node = self.reverse.step(node, byte);
+            // Reversed patterns ending here are patterns starting at i.
+            let longest = self.reverse.nodes[node]
+                .outputs
+                .iter()
+                .copied()
+                .max_by_key(|pattern| (self.lengths[*pattern], Reverse(*pattern)));
+            if let Some(pattern) = longest {
+                return Some(Match {
+                    pattern,
+                    start: i,
+                    end: i + self.lengths[pattern],
+                });
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(matcher: &Matcher, text: &str) -> Vec<(usize, Range<usize>)> {
+        matcher
+            .find_iter(text)
+            .map(|found| (found.pattern, found.span()))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_iter_overlapping() {
+        let matcher = Matcher::new(["he", "she", "his", "hers"]);
+        assert_eq!(
+            spans(&matcher, "ushers"),
+            vec![(0, 2..4), (1, 1..4), (3, 2..6)]
+        );
+        let digits = Matcher::new(["one", "two", "eight", "2"]);
+        assert_eq!(
+            spans(&digits, "eightwone2"),
+            vec![(2, 0..5), (1, 4..7), (0, 6..9), (3, 9..10)]
+        );
+        assert_eq!(spans(&digits, "nothing here"), vec![]);
+    }
+
+    #[test]
+    fn test_find_first_and_last() {
+        let matcher = Matcher::new(["abcd", "bc", "c", "cde"]);
+        let first = matcher.find_first("xabcde").unwrap();
+        assert_eq!((first.pattern, first.span()), (0, 1..5));
+        let last = matcher.find_last("xabcde").unwrap();
+        assert_eq!((last.pattern, last.span()), (3, 3..6));
+
+        let digits = Matcher::new(["one", "two", "eight", "1"]);
+        assert_eq!(digits.find_first("xeightwone").unwrap().pattern, 2);
+        assert_eq!(digits.find_last("xeightwone").unwrap().pattern, 0);
+        assert_eq!(digits.find_last("1xx").unwrap().span(), 0..1);
+        assert_eq!(
+            digits.find_first("none"),
+            Some(Match {
+                pattern: 0,
+                start: 1,
+                end: 4
+            })
+        );
+        assert_eq!(digits.find_first("zzz"), None);
+        assert_eq!(digits.find_last(""), None);
+    }
+
+    #[test]
+    fn test_unicode_and_empty() {
+        let matcher = Matcher::new(["drei", "zwölf", "ölf"]);
+        assert_eq!(
+            spans(&matcher, "zwölfdrei"),
+            vec![(1, 0..6), (2, 2..6), (0, 6..10)]
+        );
+        let none = Matcher::new(Vec::<&str>::new());
+        assert!(none.is_empty());
+        assert_eq!(none.find_first("abc"), None);
+        assert_eq!(none.find_last("abc"), None);
+    }
+}
@@ -0,0 +1,117 @@
+//! View of a grid repeated forever in every direction, for maps that tile like
+//! the garden in day 21. Any coordinate is on the view, it just wraps around to
+//! the matching cell of the grid underneath.
+
+use std::ops::Index;
+
+use crate::coord::Coord;
+use crate::grid::Grid;
+
+/// View of a grid copied out infinitely in all directions. Made by
+/// [Grid::tiled]. The copy the grid itself sits in is tile (0, 0), the one to
+/// its left is (-1, 0) and so on.
+#[derive(Clone, Copy, Debug)]
+pub struct TiledGrid<'a, T> {
+    grid: &'a Grid<T>,
+}
+
+impl<T> Grid<T> {
+    /// Get a view that repeats the grid forever in every direction.
+    pub fn tiled(&self) -> TiledGrid<'_, T> {
+        TiledGrid { grid: self }
+    }
+}
+
+impl<'a, T> TiledGrid<'a, T> {
+    /// Get the grid being repeated.
+    pub fn grid(&self) -> &'a Grid<T> {
+        self.grid
+    }
+
+    /// Get the coordinate on the underlying grid that the coordinate wraps to.
+    /// Panics if the grid is empty.
+    pub fn wrap(&self, coord: &Coord) -> Coord {
+        let (origin, size) = self.origin_and_size();
+        origin
+            + Coord::new(
+                (coord.x - origin.x).rem_euclid(size.x),
+                (coord.y - origin.y).rem_euclid(size.y),
+            )
+    }
+
+    /// Get which copy of the grid the coordinate is in. Panics if the grid is
+    /// empty.
+    pub fn tile(&self, coord: &Coord) -> Coord {
+        let (origin, size) = self.origin_and_size();
+        Coord::new(
+            (coord.x - origin.x).div_euclid(size.x),
+            (coord.y - origin.y).div_euclid(size.y),
+        )
+    }
+
+    /// Top left of the grid and its width and height.
+    fn origin_and_size(&self) -> (Coord, Coord) {
+        let bounds = self.grid.bounds().expect("An empty grid can't be tiled.");
+        (bounds.min, Coord::new(bounds.width(), bounds.height()))
+    }
+
+    /// Every coordinate is on the view.
+    pub fn has_coord(&self, _coord: &Coord) -> bool {
+        true
+    }
+
+    /// Get the cell the coordinate wraps to. Only None if the grid is empty.
+    pub fn get(&self, coord: &Coord) -> Option<&'a T> {
+        if self.grid.is_empty() {
+            return None;
+        }
+        self.grid.get(&self.wrap(coord))
+    }
+}
+
+impl<T> Index<Coord> for TiledGrid<'_, T> {
+    type Output = T;
+
+    /// Get the cell the coordinate wraps to. Panics if the grid is empty.
+    fn index(&self, coord: Coord) -> &T {
+        &self.grid[self.wrap(&coord)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_and_tile() {
+        let grid = Grid::new_from_string("abc\ndef");
+        let tiled = grid.tiled();
+        assert_eq!(tiled[Coord::new(0, 0)], 'a');
+        assert_eq!(tiled[Coord::new(3, 0)], 'a');
+        assert_eq!(tiled[Coord::new(-1, 0)], 'c');
+        assert_eq!(tiled[Coord::new(-1, -1)], 'f');
+        assert_eq!(tiled[Coord::new(7, -4)], 'b');
+        assert_eq!(tiled.wrap(&Coord::new(-4, 5)), Coord::new(2, 1));
+        assert_eq!(tiled.tile(&Coord::new(2, 1)), Coord::new(0, 0));
+        assert_eq!(tiled.tile(&Coord::new(-1, 0)), Coord::new(-1, 0));
+        assert_eq!(tiled.tile(&Coord::new(-4, 5)), Coord::new(-2, 2));
+        assert!(tiled.has_coord(&Coord::new(i32::MIN, 0)));
+    }
+
+    #[test]
+    fn test_wrap_with_origin() {
+        let grid = Grid::new_from_string("ab\ncd").with_origin(Coord::new(-5, 10));
+        let tiled = grid.tiled();
+        assert_eq!(tiled[Coord::new(0, 0)], 'b');
+        assert_eq!(tiled.wrap(&Coord::new(0, 0)), Coord::new(-4, 10));
+        assert_eq!(tiled.tile(&Coord::new(-5, 10)), Coord::new(0, 0));
+        assert_eq!(tiled.tile(&Coord::new(-6, 9)), Coord::new(-1, -1));
+    }
+
+    #[test]
+    fn test_empty() {
+        let grid: Grid = Grid::new();
+        assert_eq!(grid.tiled().get(&Coord::new(0, 0)), None);
+        assert!(grid.tiled().has_coord(&Coord::new(0, 0)));
+    }
+}
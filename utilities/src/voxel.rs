@@ -0,0 +1,196 @@
+//! Sparse grid for 3D puzzles. Only the cells that have something in them are
+//! stored, so the space can be huge or have negative coordinates.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::coord::{Coord, Coord3};
+use crate::grid::Grid;
+use crate::rect::Rect;
+use crate::search::bfs;
+
+/// Structure representing a 3D grid of any cell type where most of the space is
+/// empty.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VoxelGrid<T> {
+    cells: HashMap<Coord3, T>,
+}
+
+impl<T> VoxelGrid<T> {
+    /// Make a new empty grid.
+    pub fn new() -> Self {
+        Self {
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Number of cells that are filled.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Grid has no cells.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Put a cell at the coordinate, returning the one that was there.
+    pub fn insert(&mut self, coord: Coord3, cell: T) -> Option<T> {
+        self.cells.insert(coord, cell)
+    }
+
+    /// Take the cell out of the coordinate.
+    pub fn remove(&mut self, coord: &Coord3) -> Option<T> {
+        self.cells.remove(coord)
+    }
+
+    /// Grid has a cell at the coordinate.
+    pub fn contains(&self, coord: &Coord3) -> bool {
+        self.cells.contains_key(coord)
+    }
+
+    /// Get the cell at the coordinate.
+    pub fn get(&self, coord: &Coord3) -> Option<&T> {
+        self.cells.get(coord)
+    }
+
+    /// Get a mutable reference to the cell at the coordinate.
+    pub fn get_mut(&mut self, coord: &Coord3) -> Option<&mut T> {
+        self.cells.get_mut(coord)
+    }
+
+    /// Iterate the filled coordinates and their cells in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord3, &T)> {
+        self.cells.iter().map(|(coord, cell)| (*coord, cell))
+    }
+
+    /// Get the smallest and largest corners of the box holding every cell, or
+    /// None if the grid is empty.
+    pub fn bounds(&self) -> Option<(Coord3, Coord3)> {
+        let mut coords = self.cells.keys();
+        let first = *coords.next()?;
+        Some(coords.fold((first, first), |(min, max), coord| {
+            (
+                Coord3::new(min.x.min(coord.x), min.y.min(coord.y), min.z.min(coord.z)),
+                Coord3::new(max.x.max(coord.x), max.y.max(coord.y), max.z.max(coord.z)),
+            )
+        }))
+    }
+
+    /// Get the layer at height `z` as a 2D grid covering the x and y of the
+    /// whole bounding box, so every layer lines up. The layer keeps the x and y
+    /// of the cells.
+    pub fn layer(&self, z: i32) -> Grid<Option<&T>> {
+        let Some((min, max)) = self.bounds() else {
+            return Grid::new();
+        };
+        Grid::from_rect(Rect::new(min.xy(), max.xy()), |coord: Coord| {
+            self.get(&Coord3::new(coord.x, coord.y, z))
+        })
+    }
+
+    /// Find every coordinate reachable from `start` through the six faces that
+    /// `passable` allows, with the cell there if there is one. The fill stays
+    /// inside the bounding box grown by one on every side so it always ends,
+    /// which is enough to reach all the way around the outside of the cells.
+    pub fn flood_fill(
+        &self,
+        start: Coord3,
+        mut passable: impl FnMut(Coord3, Option<&T>) -> bool,
+    ) -> HashSet<Coord3> {
+        let Some((min, max)) = self.bounds() else {
+            return HashSet::from([start]);
+        };
+        let (min, max) = (min + Coord3::new(-1, -1, -1), max + Coord3::new(1, 1, 1));
+        let inside = |coord: &Coord3| {
+            (min.x..=max.x).contains(&coord.x)
+                && (min.y..=max.y).contains(&coord.y)
+                && (min.z..=max.z).contains(&coord.z)
+        };
+        let result = bfs(
+            [start],
+            |from: &Coord3| {
+                from.neighbors6()
+                    .filter(|to| inside(to) && passable(*to, self.get(to)))
+                    .collect::<Vec<Coord3>>()
+            },
+            |_| false,
+        );
+        result.distances.into_keys().collect()
+    }
+}
+
+impl<T> Default for VoxelGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<(Coord3, T)> for VoxelGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Coord3, T)>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hollow 3x3x3 cube of walls with its middle empty.
+    fn hollow_cube() -> VoxelGrid<char> {
+        (-1..=1)
+            .flat_map(|z| (-1..=1).flat_map(move |y| (-1..=1).map(move |x| Coord3::new(x, y, z))))
+            .filter(|coord| *coord != Coord3::new(0, 0, 0))
+            .map(|coord| (coord, '#'))
+            .collect()
+    }
+
+    #[test]
+    fn test_insert_and_bounds() {
+        let mut grid = VoxelGrid::new();
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.insert(Coord3::new(1, -2, 3), 'a'), None);
+        grid.insert(Coord3::new(-4, 5, 0), 'b');
+        assert_eq!(grid.insert(Coord3::new(1, -2, 3), 'c'), Some('a'));
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.get(&Coord3::new(1, -2, 3)), Some(&'c'));
+        assert_eq!(
+            grid.bounds(),
+            Some((Coord3::new(-4, -2, 0), Coord3::new(1, 5, 3)))
+        );
+        assert_eq!(grid.remove(&Coord3::new(-4, 5, 0)), Some('b'));
+        assert!(!grid.contains(&Coord3::new(-4, 5, 0)));
+    }
+
+    #[test]
+    fn test_layer() {
+        let mut grid = hollow_cube();
+        grid.insert(Coord3::new(5, 0, 0), '@');
+        let middle = grid.layer(0);
+        assert_eq!(middle.width(), 7);
+        assert_eq!(middle.height(), 3);
+        assert_eq!(middle[Coord::new(-1, 0)], Some(&'#'));
+        assert_eq!(middle[Coord::new(5, 0)], Some(&'@'));
+        let row: Vec<char> = middle
+            .row(1)
+            .unwrap()
+            .iter()
+            .map(|c| *c.unwrap_or(&'.'))
+            .collect();
+        assert_eq!(row, vec!['#', '.', '#', '.', '.', '.', '@']);
+        assert!(grid.layer(9).iter().all(|(_, cell)| cell.is_none()));
+        assert!(VoxelGrid::<char>::new().layer(0).is_empty());
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let grid = hollow_cube();
+        let outside = grid.flood_fill(Coord3::new(-2, -2, -2), |_, cell| cell.is_none());
+        // Bounding box grown by one is 5x5x5, minus the cube.
+        assert_eq!(outside.len(), 125 - 27);
+        assert!(!outside.contains(&Coord3::new(0, 0, 0)));
+        let inside = grid.flood_fill(Coord3::new(0, 0, 0), |_, cell| cell.is_none());
+        assert_eq!(inside, HashSet::from([Coord3::new(0, 0, 0)]));
+    }
+}